@@ -0,0 +1,234 @@
+use crate::color::Color;
+use crate::light::{sample_towards, Light, LightSample};
+use crate::tuple::Tuple;
+use num::Float;
+
+/*
+    This file contains the area light. It is a rectangle that is divided
+    into cells, every cell sends light from a jittered point inside of it.
+    Parts of the light can be hidden from a point, which gives soft shadows
+*/
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AreaLight<T>
+where
+    T: Float,
+{
+    corner: Tuple<T>,
+    // the edges of a single cell
+    uvec: Tuple<T>,
+    vvec: Tuple<T>,
+    usteps: usize,
+    vsteps: usize,
+    pub intensity: Color,
+    // the jitter only depends on the seed and the point that is
+    // lit, so the same seed renders the same image every time
+    pub seed: u64,
+}
+
+impl<T> AreaLight<T>
+where
+    T: Float,
+{
+    // create a rectangular light from a corner and its two edges, the
+    // edges are divided into usteps and vsteps cells. there is always
+    // at least one cell along every edge
+    pub fn new(
+        corner: Tuple<T>,
+        full_uvec: Tuple<T>,
+        usteps: usize,
+        full_vvec: Tuple<T>,
+        vsteps: usize,
+        intensity: Color,
+    ) -> Self {
+        let (usteps, vsteps) = (usteps.max(1), vsteps.max(1));
+        Self {
+            corner,
+            uvec: full_uvec / T::from(usteps).unwrap(),
+            vvec: full_vvec / T::from(vsteps).unwrap(),
+            usteps,
+            vsteps,
+            intensity,
+            seed: 0,
+        }
+    }
+
+    pub fn corner(&self) -> Tuple<T> {
+        self.corner
+    }
+
+    pub fn usteps(&self) -> usize {
+        self.usteps
+    }
+
+    pub fn vsteps(&self) -> usize {
+        self.vsteps
+    }
+
+    // the number of samples that are taken of the light
+    pub fn sample_count(&self) -> usize {
+        self.usteps * self.vsteps
+    }
+
+    // the center of the light
+    pub fn position(&self) -> Tuple<T> {
+        let half = T::from(0.5).unwrap();
+        self.corner
+            + self.uvec * (T::from(self.usteps).unwrap() * half)
+            + self.vvec * (T::from(self.vsteps).unwrap() * half)
+    }
+
+    // return a point inside of the cell, the offsets are between 0 and 1
+    pub fn point_on_light(&self, u: usize, v: usize, u_offset: T, v_offset: T) -> Tuple<T> {
+        self.corner
+            + self.uvec * (T::from(u).unwrap() + u_offset)
+            + self.vvec * (T::from(v).unwrap() + v_offset)
+    }
+
+    // return the jittered sample positions of every cell as seen from the point
+    pub fn sample_positions(&self, point: Tuple<T>) -> Vec<Tuple<T>> {
+        let mut state = [point.x, point.y, point.z]
+            .iter()
+            .fold(self.seed, |state, value| {
+                mix(state ^ value.to_f64().unwrap().to_bits())
+            });
+        let mut next = || {
+            state = mix(state);
+            T::from(unit_float(state)).unwrap()
+        };
+
+        let mut positions = Vec::with_capacity(self.sample_count());
+        for v in 0..self.vsteps {
+            for u in 0..self.usteps {
+                let (u_offset, v_offset) = (next(), next());
+                positions.push(self.point_on_light(u, v, u_offset, v_offset))
+            }
+        }
+        positions
+    }
+}
+
+impl<T> Light<T> for AreaLight<T>
+where
+    T: Float,
+    T: Send + Sync,
+{
+    fn intensity(&self) -> &Color {
+        &self.intensity
+    }
+
+    fn samples(&self, point: Tuple<T>) -> Vec<LightSample<T>> {
+        self.sample_positions(point)
+            .into_iter()
+            .map(|position| sample_towards(position, point, self.intensity.clone()))
+            .collect()
+    }
+}
+
+// the finalizer of splitmix64, it scrambles the bits of the state
+// so that states that are close give very different numbers
+fn mix(state: u64) -> u64 {
+    let mut z = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+// turn the upper 53 bits into a float between 0 and 1, 1 excluded
+fn unit_float(value: u64) -> f64 {
+    (value >> 11) as f64 / (1u64 << 53) as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::area_light::AreaLight;
+    use crate::color::Color;
+    use crate::light::Light;
+    use crate::tuple::Tuple;
+
+    fn area_light() -> AreaLight<f64> {
+        AreaLight::new(
+            Tuple::new_point(0.0, 0.0, 0.0),
+            Tuple::new_vector(2.0, 0.0, 0.0),
+            4,
+            Tuple::new_vector(0.0, 0.0, 1.0),
+            2,
+            Color::new(1.0, 1.0, 1.0),
+        )
+    }
+
+    #[test]
+    fn create_area_light() {
+        let light = area_light();
+
+        assert_eq!(light.corner(), Tuple::new_point(0.0, 0.0, 0.0));
+        assert_eq!(light.usteps(), 4);
+        assert_eq!(light.vsteps(), 2);
+        assert_eq!(light.sample_count(), 8);
+        assert_eq!(light.position(), Tuple::new_point(1.0, 0.0, 0.5));
+        assert_eq!(light.intensity, Color::new(1.0, 1.0, 1.0))
+    }
+
+    #[test]
+    fn find_point_on_area_light() {
+        let light = area_light();
+        let parameters = [
+            ((0, 0), (0.25, 0.0, 0.25)),
+            ((1, 0), (0.75, 0.0, 0.25)),
+            ((0, 1), (0.25, 0.0, 0.75)),
+            ((2, 0), (1.25, 0.0, 0.25)),
+            ((3, 1), (1.75, 0.0, 0.75)),
+        ];
+
+        for ((u, v), (x, y, z)) in parameters {
+            assert_eq!(
+                light.point_on_light(u, v, 0.5, 0.5),
+                Tuple::new_point(x, y, z)
+            )
+        }
+    }
+
+    #[test]
+    fn samples_are_jittered_inside_their_cells() {
+        let light = area_light();
+        let positions = light.sample_positions(Tuple::new_point(0.3, -2.0, 1.7));
+
+        assert_eq!(positions.len(), 8);
+        for (index, position) in positions.iter().enumerate() {
+            let (u, v) = ((index % 4) as f64, (index / 4) as f64);
+            assert!(position.x >= u * 0.5 && position.x < (u + 1.0) * 0.5);
+            assert!(position.z >= v * 0.5 && position.z < (v + 1.0) * 0.5);
+            assert_eq!(position.y, 0.0)
+        }
+        // the jitter moves the samples away from the centers of the cells
+        assert!(positions
+            .iter()
+            .enumerate()
+            .any(|(index, position)| position.x != (index % 4) as f64 * 0.5 + 0.25))
+    }
+
+    #[test]
+    fn samples_depend_on_seed() {
+        let mut light = area_light();
+        let point = Tuple::new_point(0.3, -2.0, 1.7);
+        let first = light.sample_positions(point);
+
+        assert_eq!(light.sample_positions(point), first);
+        light.seed = 42;
+        assert_ne!(light.sample_positions(point), first)
+    }
+
+    #[test]
+    fn light_sample_points_towards_light() {
+        let light = area_light();
+        let point = Tuple::new_point(1.0, -4.0, 0.5);
+        let samples = light.samples(point);
+
+        assert_eq!(samples.len(), 8);
+        for (sample, position) in samples.iter().zip(light.sample_positions(point)) {
+            assert_eq!(sample.direction, (position - point).normalize());
+            assert_eq!(sample.distance, (position - point).magnitude());
+            assert_eq!(sample.intensity, light.intensity)
+        }
+    }
+}
@@ -0,0 +1,416 @@
+use crate::ray::Ray;
+use crate::{Matrix, Tuple};
+use num::Float;
+use std::ops::AddAssign;
+
+/*
+    This file contains the axis aligned bounding box. Every shape has a box
+    around it, a ray that misses the box can not hit anything inside of it
+    so shapes with many children can skip testing them
+*/
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Aabb<T>
+where
+    T: Float,
+{
+    pub min: Tuple<T>,
+    pub max: Tuple<T>,
+}
+
+impl<T> Aabb<T>
+where
+    T: Float,
+    T: AddAssign,
+{
+    pub fn new(min: Tuple<T>, max: Tuple<T>) -> Self {
+        Self { min, max }
+    }
+
+    // create a box that contains nothing, adding a
+    // point or a box to it makes it fit around them
+    pub fn empty() -> Self {
+        Self {
+            min: Tuple::new_point(T::infinity(), T::infinity(), T::infinity()),
+            max: Tuple::new_point(T::neg_infinity(), T::neg_infinity(), T::neg_infinity()),
+        }
+    }
+
+    // create a box that contains everything, used for shapes like planes
+    pub fn infinite() -> Self {
+        Self {
+            min: Tuple::new_point(T::neg_infinity(), T::neg_infinity(), T::neg_infinity()),
+            max: Tuple::new_point(T::infinity(), T::infinity(), T::infinity()),
+        }
+    }
+
+    // a box is empty when its minimum is larger than its maximum on any axis
+    pub fn is_empty(&self) -> bool {
+        (0..3).any(|axis| self.min[axis] > self.max[axis])
+    }
+
+    // a box is finite when none of its sides is at infinity
+    pub fn is_finite(&self) -> bool {
+        (0..3).all(|axis| self.min[axis].is_finite() && self.max[axis].is_finite())
+    }
+
+    // grow the box so it contains the point
+    pub fn add_point(&mut self, point: Tuple<T>) {
+        self.min = Tuple::new_point(
+            self.min.x.min(point.x),
+            self.min.y.min(point.y),
+            self.min.z.min(point.z),
+        );
+        self.max = Tuple::new_point(
+            self.max.x.max(point.x),
+            self.max.y.max(point.y),
+            self.max.z.max(point.z),
+        );
+    }
+
+    // return the smallest box that contains both boxes
+    pub fn union(&self, other: &Aabb<T>) -> Aabb<T> {
+        let mut result = *self;
+        result.add_point(other.min);
+        result.add_point(other.max);
+        result
+    }
+
+    pub fn contains_point(&self, point: Tuple<T>) -> bool {
+        (0..3).all(|axis| self.min[axis] <= point[axis] && point[axis] <= self.max[axis])
+    }
+
+    pub fn contains_box(&self, other: &Aabb<T>) -> bool {
+        self.contains_point(other.min) && self.contains_point(other.max)
+    }
+
+    // return the box that fits around this box after it has been
+    // transformed, every corner is transformed and boxed again. an
+    // infinite box stays infinite because a rotation spreads the
+    // infinity to the other axes
+    pub fn transform(&self, transformation: &Matrix<T, 4>) -> Aabb<T> {
+        if self.is_empty() {
+            return *self;
+        }
+        if !self.is_finite() {
+            return Self::infinite();
+        }
+
+        let mut result = Self::empty();
+        for x in [self.min.x, self.max.x] {
+            for y in [self.min.y, self.max.y] {
+                for z in [self.min.z, self.max.z] {
+                    result.add_point(transformation.clone() * Tuple::new_point(x, y, z))
+                }
+            }
+        }
+        result
+    }
+
+    // return the values where the ray enters and leaves the box. the box
+    // is only used to skip work, so the test is conservative: it only
+    // reports a miss when the ray really can not touch the box
+    pub fn intersection_range(&self, ray: &Ray<T>) -> Option<(T, T)> {
+        if self.is_empty() {
+            return None;
+        }
+
+        let (xtmin, xtmax) = slab(ray.origin.x, ray.direction.x, self.min.x, self.max.x)?;
+        let (ytmin, ytmax) = slab(ray.origin.y, ray.direction.y, self.min.y, self.max.y)?;
+        let (ztmin, ztmax) = slab(ray.origin.z, ray.direction.z, self.min.z, self.max.z)?;
+
+        let tmin = xtmin.max(ytmin).max(ztmin);
+        let tmax = xtmax.min(ytmax).min(ztmax);
+
+        // comparisons with NaN are false, so a NaN is never culled
+        match tmin > tmax {
+            true => None,
+            false => Some((tmin, tmax)),
+        }
+    }
+
+    pub fn intersects(&self, ray: &Ray<T>) -> bool {
+        self.intersection_range(ray).is_some()
+    }
+
+    // return the surface area of the box, an empty box has no area
+    pub fn surface_area(&self) -> T {
+        if self.is_empty() {
+            return T::zero();
+        }
+        let size = self.max - self.min;
+        T::from(2.0).unwrap() * (size.x * size.y + size.y * size.z + size.z * size.x)
+    }
+
+    // return the center point of the box
+    pub fn centroid(&self) -> Tuple<T> {
+        let half = T::from(0.5).unwrap();
+        Tuple::new_point(
+            (self.min.x + self.max.x) * half,
+            (self.min.y + self.max.y) * half,
+            (self.min.z + self.max.z) * half,
+        )
+    }
+}
+
+// return the values where the ray enters and leaves the slab between min
+// and max on a single axis. only a direction of exactly zero is parallel,
+// such a ray is inside of the slab everywhere or misses it completely
+fn slab<T>(origin: T, direction: T, min: T, max: T) -> Option<(T, T)>
+where
+    T: Float,
+{
+    if direction == T::zero() {
+        return match origin < min || origin > max {
+            true => None,
+            false => Some((T::neg_infinity(), T::infinity())),
+        };
+    }
+    let t1 = (min - origin) / direction;
+    let t2 = (max - origin) / direction;
+    match t1 > t2 {
+        true => Some((t2, t1)),
+        false => Some((t1, t2)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bounds::Aabb;
+    use crate::ray::Ray;
+    use crate::{Matrix, Tuple};
+    use std::f64::consts::{FRAC_1_SQRT_2, PI, SQRT_2};
+
+    #[test]
+    fn create_empty_box() {
+        let aabb = Aabb::<f64>::empty();
+
+        assert!(aabb.is_empty());
+        assert!(!aabb.contains_point(Tuple::new_point(0.0, 0.0, 0.0)))
+    }
+
+    #[test]
+    fn add_points_to_empty_box() {
+        let mut aabb = Aabb::<f64>::empty();
+        aabb.add_point(Tuple::new_point(-5.0, 2.0, 0.0));
+        aabb.add_point(Tuple::new_point(7.0, 0.0, -3.0));
+
+        assert_eq!(aabb.min, Tuple::new_point(-5.0, 0.0, -3.0));
+        assert_eq!(aabb.max, Tuple::new_point(7.0, 2.0, 0.0))
+    }
+
+    #[test]
+    fn union_of_boxes() {
+        let aabb_1 = Aabb::new(
+            Tuple::<f64>::new_point(-5.0, -2.0, 0.0),
+            Tuple::new_point(7.0, 4.0, 4.0),
+        );
+        let aabb_2 = Aabb::new(
+            Tuple::new_point(8.0, -7.0, -2.0),
+            Tuple::new_point(14.0, 2.0, 8.0),
+        );
+        let union = aabb_1.union(&aabb_2);
+
+        assert_eq!(union.min, Tuple::new_point(-5.0, -7.0, -2.0));
+        assert_eq!(union.max, Tuple::new_point(14.0, 4.0, 8.0))
+    }
+
+    #[test]
+    fn box_contains_point() {
+        let aabb = Aabb::new(
+            Tuple::<f64>::new_point(5.0, -2.0, 0.0),
+            Tuple::new_point(11.0, 4.0, 7.0),
+        );
+        let parameters = [
+            ((5.0, -2.0, 0.0), true),
+            ((11.0, 4.0, 7.0), true),
+            ((8.0, 1.0, 3.0), true),
+            ((3.0, 0.0, 3.0), false),
+            ((8.0, -4.0, 3.0), false),
+            ((8.0, 1.0, -1.0), false),
+            ((13.0, 1.0, 3.0), false),
+            ((8.0, 5.0, 3.0), false),
+            ((8.0, 1.0, 8.0), false),
+        ];
+
+        for ((x, y, z), result) in parameters {
+            assert_eq!(aabb.contains_point(Tuple::new_point(x, y, z)), result)
+        }
+    }
+
+    #[test]
+    fn box_contains_box() {
+        let aabb = Aabb::new(
+            Tuple::<f64>::new_point(5.0, -2.0, 0.0),
+            Tuple::new_point(11.0, 4.0, 7.0),
+        );
+        let parameters = [
+            ((5.0, -2.0, 0.0), (11.0, 4.0, 7.0), true),
+            ((6.0, -1.0, 1.0), (10.0, 3.0, 6.0), true),
+            ((4.0, -3.0, -1.0), (10.0, 3.0, 6.0), false),
+            ((6.0, -1.0, 1.0), (12.0, 5.0, 8.0), false),
+        ];
+
+        for ((x1, y1, z1), (x2, y2, z2), result) in parameters {
+            let other = Aabb::new(Tuple::new_point(x1, y1, z1), Tuple::new_point(x2, y2, z2));
+
+            assert_eq!(aabb.contains_box(&other), result)
+        }
+    }
+
+    #[test]
+    fn transform_box() {
+        let aabb = Aabb::new(
+            Tuple::<f64>::new_point(-1.0, -1.0, -1.0),
+            Tuple::new_point(1.0, 1.0, 1.0),
+        );
+        let transformed =
+            aabb.transform(&(Matrix::rotate_x(PI / 4.0) * Matrix::rotate_y(PI / 4.0)));
+
+        let value = 1.0 + FRAC_1_SQRT_2;
+
+        assert_eq!(transformed.min, Tuple::new_point(-SQRT_2, -value, -value));
+        assert_eq!(transformed.max, Tuple::new_point(SQRT_2, value, value))
+    }
+
+    #[test]
+    fn transform_infinite_box() {
+        let aabb = Aabb::<f64>::infinite();
+        let transformed = aabb.transform(&Matrix::rotate_x(PI / 4.0));
+
+        // infinite values can not be compared with the epsilon of a tuple
+        for axis in 0..3 {
+            assert_eq!(transformed.min[axis], f64::NEG_INFINITY);
+            assert_eq!(transformed.max[axis], f64::INFINITY)
+        }
+    }
+
+    #[test]
+    fn ray_intersects_cubic_box() {
+        let aabb = Aabb::new(
+            Tuple::<f64>::new_point(-1.0, -1.0, -1.0),
+            Tuple::new_point(1.0, 1.0, 1.0),
+        );
+        let parameters = [
+            ((5.0, 0.5, 0.0), (-1.0, 0.0, 0.0), true),
+            ((-5.0, 0.5, 0.0), (1.0, 0.0, 0.0), true),
+            ((0.5, 5.0, 0.0), (0.0, -1.0, 0.0), true),
+            ((0.5, -5.0, 0.0), (0.0, 1.0, 0.0), true),
+            ((0.5, 0.0, 5.0), (0.0, 0.0, -1.0), true),
+            ((0.5, 0.0, -5.0), (0.0, 0.0, 1.0), true),
+            ((0.0, 0.5, 0.0), (0.0, 0.0, 1.0), true),
+            ((-2.0, 0.0, 0.0), (2.0, 4.0, 6.0), false),
+            ((0.0, -2.0, 0.0), (6.0, 2.0, 4.0), false),
+            ((0.0, 0.0, -2.0), (4.0, 6.0, 2.0), false),
+            ((2.0, 0.0, 2.0), (0.0, 0.0, -1.0), false),
+            ((0.0, 2.0, 2.0), (0.0, -1.0, 0.0), false),
+            ((2.0, 2.0, 0.0), (-1.0, 0.0, 0.0), false),
+        ];
+
+        for ((ox, oy, oz), (dx, dy, dz), result) in parameters {
+            let direction = Tuple::new_vector(dx, dy, dz).normalize();
+            let ray = Ray::new(Tuple::new_point(ox, oy, oz), direction);
+
+            assert_eq!(aabb.intersects(&ray), result)
+        }
+    }
+
+    #[test]
+    fn ray_intersects_non_cubic_box() {
+        let aabb = Aabb::new(
+            Tuple::<f64>::new_point(5.0, -2.0, 0.0),
+            Tuple::new_point(11.0, 4.0, 7.0),
+        );
+        let parameters = [
+            ((15.0, 1.0, 2.0), (-1.0, 0.0, 0.0), true),
+            ((-5.0, -1.0, 4.0), (1.0, 0.0, 0.0), true),
+            ((7.0, 6.0, 5.0), (0.0, -1.0, 0.0), true),
+            ((9.0, -5.0, 6.0), (0.0, 1.0, 0.0), true),
+            ((8.0, 2.0, 12.0), (0.0, 0.0, -1.0), true),
+            ((6.0, 0.0, -5.0), (0.0, 0.0, 1.0), true),
+            ((8.0, 1.0, 3.5), (0.0, 0.0, 1.0), true),
+            ((9.0, -1.0, -8.0), (2.0, 4.0, 6.0), false),
+            ((8.0, 3.0, -4.0), (6.0, 2.0, 4.0), false),
+            ((9.0, -1.0, -2.0), (4.0, 6.0, 2.0), false),
+            ((4.0, 0.0, 9.0), (0.0, 0.0, -1.0), false),
+            ((8.0, 6.0, -1.0), (0.0, -1.0, 0.0), false),
+            ((12.0, 5.0, 4.0), (-1.0, 0.0, 0.0), false),
+        ];
+
+        for ((ox, oy, oz), (dx, dy, dz), result) in parameters {
+            let direction = Tuple::new_vector(dx, dy, dz).normalize();
+            let ray = Ray::new(Tuple::new_point(ox, oy, oz), direction);
+
+            assert_eq!(aabb.intersects(&ray), result)
+        }
+    }
+
+    #[test]
+    fn range_where_ray_is_inside_box() {
+        let aabb = Aabb::new(
+            Tuple::<f64>::new_point(-1.0, -1.0, -1.0),
+            Tuple::new_point(1.0, 1.0, 1.0),
+        );
+        let ray = Ray::new(
+            Tuple::new_point(0.0, 0.0, -5.0),
+            Tuple::new_vector(0.0, 0.0, 1.0),
+        );
+
+        assert_eq!(aabb.intersection_range(&ray), Some((4.0, 6.0)))
+    }
+
+    #[test]
+    fn surface_area_and_centroid_of_box() {
+        let aabb = Aabb::new(
+            Tuple::<f64>::new_point(-1.0, 0.0, 2.0),
+            Tuple::new_point(1.0, 3.0, 6.0),
+        );
+
+        assert_eq!(aabb.surface_area(), 2.0 * (6.0 + 12.0 + 8.0));
+        assert_eq!(aabb.centroid(), Tuple::new_point(0.0, 1.5, 4.0));
+        assert_eq!(Aabb::<f64>::empty().surface_area(), 0.0)
+    }
+
+    #[test]
+    fn ray_always_intersects_infinite_box() {
+        let aabb = Aabb::<f64>::infinite();
+        let ray = Ray::new(
+            Tuple::new_point(3.0, 100.0, -2.0),
+            Tuple::new_vector(0.0, 1.0, 0.0),
+        );
+
+        assert!(aabb.intersects(&ray))
+    }
+
+    #[test]
+    fn nearly_parallel_ray_intersects_box() {
+        let aabb = Aabb::new(
+            Tuple::<f64>::new_point(-1.0, -1.0, -1.0),
+            Tuple::new_point(1.0, 1.0, 1.0),
+        );
+        let ray = Ray::new(
+            Tuple::new_point(1.00001, 0.0, -5.0),
+            Tuple::new_vector(-5e-6, 0.0, 1.0),
+        );
+
+        assert_eq!(aabb.intersection_range(&ray), Some((4.0, 6.0)))
+    }
+
+    #[test]
+    fn parallel_ray_only_intersects_box_from_inside_slab() {
+        let aabb = Aabb::new(
+            Tuple::<f64>::new_point(-1.0, -1.0, -1.0),
+            Tuple::new_point(1.0, 1.0, 1.0),
+        );
+        let parameters = [(1.0, true), (0.5, true), (1.00001, false), (-1.5, false)];
+
+        for (x, result) in parameters {
+            let ray = Ray::new(
+                Tuple::new_point(x, 0.0, -5.0),
+                Tuple::new_vector(0.0, 0.0, 1.0),
+            );
+
+            assert_eq!(aabb.intersects(&ray), result)
+        }
+    }
+}
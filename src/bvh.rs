@@ -0,0 +1,442 @@
+use crate::bounds::Aabb;
+use crate::intersection::Intersections;
+use crate::ray::Ray;
+use crate::shape::Shape;
+use crate::Tuple;
+use num::Float;
+use std::cmp::Ordering;
+use std::ops::AddAssign;
+
+/*
+    This file contains the bounding volume hierarchy. The objects of a world
+    are sorted into a tree of boxes so a ray only tests the objects in the
+    boxes it passes through. The tree is stored as a flat list of nodes, the
+    first child of a node is always the node right after it
+*/
+
+// nodes with this many objects or less are not split any further
+const MAX_LEAF_SIZE: usize = 4;
+// the number of buckets the objects are sorted into when looking for
+// the split with the lowest surface area cost
+const BUCKETS: usize = 12;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum BvhNodeKind {
+    // the objects at first..first + count in the order of the tree
+    Leaf { first: usize, count: usize },
+    // the children are split along the axis, the first child
+    // has the lower coordinates and directly follows its parent
+    Interior { second_child: usize, axis: usize },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct BvhNode<T>
+where
+    T: Float,
+{
+    bounds: Aabb<T>,
+    kind: BvhNodeKind,
+}
+
+// the size of a hierarchy, useful to see how well a scene is split up
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BvhStats {
+    pub nodes: usize,
+    pub leaves: usize,
+    pub depth: usize,
+    // objects with infinite bounds, like planes, are not in the
+    // tree and are tested by every ray
+    pub unbounded: usize,
+}
+
+// an object of the world while the tree is being built
+struct BuildItem<T>
+where
+    T: Float,
+{
+    index: usize,
+    bounds: Aabb<T>,
+    centroid: Tuple<T>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Bvh<T>
+where
+    T: Float,
+{
+    nodes: Vec<BvhNode<T>>,
+    // the indices of the objects in the order the leaves reference them
+    order: Vec<usize>,
+    unbounded: Vec<usize>,
+    depth: usize,
+}
+
+impl<T> Bvh<T>
+where
+    T: Float,
+    T: AddAssign,
+{
+    // build the hierarchy over the bounds of the objects, the objects
+    // themselves stay where they are and are referenced by index
+    pub fn build(objects: &[Box<dyn Shape<T>>]) -> Self {
+        let mut bvh = Self {
+            nodes: vec![],
+            order: vec![],
+            unbounded: vec![],
+            depth: 0,
+        };
+        let mut items = vec![];
+
+        for (index, object) in objects.iter().enumerate() {
+            let bounds = object.parent_space_bounds();
+            match bounds.is_finite() {
+                true => items.push(BuildItem {
+                    index,
+                    bounds,
+                    centroid: bounds.centroid(),
+                }),
+                false => bvh.unbounded.push(index),
+            }
+        }
+
+        if !items.is_empty() {
+            bvh.build_node(&mut items, 0, 1);
+        }
+        bvh.order = items.iter().map(|item| item.index).collect();
+        bvh
+    }
+
+    pub fn stats(&self) -> BvhStats {
+        BvhStats {
+            nodes: self.nodes.len(),
+            leaves: self
+                .nodes
+                .iter()
+                .filter(|node| matches!(node.kind, BvhNodeKind::Leaf { .. }))
+                .count(),
+            depth: self.depth,
+            unbounded: self.unbounded.len(),
+        }
+    }
+
+    // add the node for the items, which start at first in the order of the
+    // tree, and return its index. the children are added after the node
+    fn build_node(&mut self, items: &mut [BuildItem<T>], first: usize, depth: usize) -> usize {
+        self.depth = self.depth.max(depth);
+        let bounds = items
+            .iter()
+            .fold(Aabb::empty(), |bounds, item| bounds.union(&item.bounds));
+        let node_index = self.nodes.len();
+        self.nodes.push(BvhNode {
+            bounds,
+            kind: BvhNodeKind::Leaf {
+                first,
+                count: items.len(),
+            },
+        });
+
+        let (axis, split) = match split_items(items) {
+            None => return node_index,
+            Some(split) => split,
+        };
+
+        self.build_node(&mut items[..split], first, depth + 1);
+        let second_child = self.build_node(&mut items[split..], first + split, depth + 1);
+        self.nodes[node_index].kind = BvhNodeKind::Interior { second_child, axis };
+        node_index
+    }
+
+    // return all intersections of the ray with the objects. the boxes are
+    // visited front to back and a box that starts behind the closest hit
+    // found so far is skipped, so it can not contain the hit of the ray
+    pub fn intersect<'a>(
+        &self,
+        objects: &'a [Box<dyn Shape<T>>],
+        ray: Ray<T>,
+    ) -> Intersections<'a, T> {
+        let mut intersections = Intersections::empty();
+        for &index in &self.unbounded {
+            intersections.merge(objects[index].intersect(ray))
+        }
+        let mut closest = match intersections.hit() {
+            None => T::infinity(),
+            Some(hit) => hit.value,
+        };
+
+        let mut stack = match self.nodes.is_empty() {
+            true => vec![],
+            false => vec![0],
+        };
+        while let Some(node_index) = stack.pop() {
+            let node = &self.nodes[node_index];
+            match node.bounds.intersection_range(&ray) {
+                Some((tmin, tmax)) if tmin <= closest && tmax >= T::zero() => {}
+                _ => continue,
+            }
+
+            match node.kind {
+                BvhNodeKind::Leaf { first, count } => {
+                    for &index in &self.order[first..first + count] {
+                        let object_intersections = objects[index].intersect(ray);
+                        if let Some(hit) = object_intersections.hit() {
+                            closest = closest.min(hit.value)
+                        }
+                        intersections.merge(object_intersections)
+                    }
+                }
+                BvhNodeKind::Interior { second_child, axis } => {
+                    // the child that is closer to the origin is popped first
+                    match ray.direction[axis] < T::zero() {
+                        true => stack.extend([node_index + 1, second_child]),
+                        false => stack.extend([second_child, node_index + 1]),
+                    }
+                }
+            }
+        }
+        intersections
+    }
+
+    // return true when any object is hit by the ray before the given
+    // distance. the first hit that is found ends the search
+    pub fn any_hit(&self, objects: &[Box<dyn Shape<T>>], ray: Ray<T>, distance: T) -> bool {
+        let hits = |index: &usize| objects[*index].any_hit(ray, distance);
+        if self.unbounded.iter().any(hits) {
+            return true;
+        }
+
+        let mut stack = match self.nodes.is_empty() {
+            true => vec![],
+            false => vec![0],
+        };
+        while let Some(node_index) = stack.pop() {
+            let node = &self.nodes[node_index];
+            match node.bounds.intersection_range(&ray) {
+                Some((tmin, tmax)) if tmin < distance && tmax > T::zero() => {}
+                _ => continue,
+            }
+
+            match node.kind {
+                BvhNodeKind::Leaf { first, count } => {
+                    if self.order[first..first + count].iter().any(hits) {
+                        return true;
+                    }
+                }
+                BvhNodeKind::Interior { second_child, .. } => {
+                    stack.extend([second_child, node_index + 1])
+                }
+            }
+        }
+        false
+    }
+}
+
+// decide where the items are split. the items are sorted along the longest
+// axis of their centers and the split with the lowest surface area cost is
+// picked. returns None when the items are better off in a single leaf
+fn split_items<T>(items: &mut [BuildItem<T>]) -> Option<(usize, usize)>
+where
+    T: Float,
+    T: AddAssign,
+{
+    if items.len() <= MAX_LEAF_SIZE {
+        return None;
+    }
+
+    let mut centroid_bounds = Aabb::empty();
+    items
+        .iter()
+        .for_each(|item| centroid_bounds.add_point(item.centroid));
+    let extent = centroid_bounds.max - centroid_bounds.min;
+    let axis = match extent {
+        e if e.x >= e.y && e.x >= e.z => 0,
+        e if e.y >= e.z => 1,
+        _ => 2,
+    };
+    items.sort_by(|a, b| {
+        a.centroid[axis]
+            .partial_cmp(&b.centroid[axis])
+            .unwrap_or(Ordering::Equal)
+    });
+
+    // every center is at the same spot, sorting them into
+    // buckets is not possible so they are split in half
+    if extent[axis] <= T::zero() {
+        return Some((axis, items.len() / 2));
+    }
+
+    let buckets = T::from(BUCKETS).unwrap();
+    let bucket_of = |item: &BuildItem<T>| {
+        let offset = (item.centroid[axis] - centroid_bounds.min[axis]) / extent[axis];
+        (offset * buckets).to_usize().unwrap_or(0).min(BUCKETS - 1)
+    };
+    let mut counts = [0; BUCKETS];
+    let mut bounds = [Aabb::empty(); BUCKETS];
+    for item in items.iter() {
+        let bucket = bucket_of(item);
+        counts[bucket] += 1;
+        bounds[bucket] = bounds[bucket].union(&item.bounds);
+    }
+
+    // the cost of a split is the chance a ray hits a side, which is
+    // the surface area of the side, times the objects on that side
+    let mut best: Option<(T, usize)> = None;
+    for last in 0..BUCKETS - 1 {
+        let (left_count, right_count): (usize, usize) = (
+            counts[..=last].iter().sum(),
+            counts[last + 1..].iter().sum(),
+        );
+        if left_count == 0 || right_count == 0 {
+            continue;
+        }
+        let left = bounds[..=last]
+            .iter()
+            .fold(Aabb::empty(), |result, b| result.union(b));
+        let right = bounds[last + 1..]
+            .iter()
+            .fold(Aabb::empty(), |result, b| result.union(b));
+        let cost = left.surface_area() * T::from(left_count).unwrap()
+            + right.surface_area() * T::from(right_count).unwrap();
+
+        let better = match best {
+            None => true,
+            Some((best_cost, _)) => cost < best_cost,
+        };
+        if better {
+            best = Some((cost, left_count))
+        }
+    }
+
+    match best {
+        Some((_, split)) => Some((axis, split)),
+        None => Some((axis, items.len() / 2)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bvh::Bvh;
+    use crate::plane::Plane;
+    use crate::ray::Ray;
+    use crate::shape::Shape;
+    use crate::sphere::Sphere;
+    use crate::{Matrix, Tuple};
+
+    // a row of spheres along the x axis, the sphere at
+    // index i is centered at x = 3 * i
+    fn sphere_row(count: usize) -> Vec<Box<dyn Shape<f64>>> {
+        (0..count)
+            .map(|index| {
+                let mut sphere = Sphere::new(index as i32);
+                sphere
+                    .set_transformation(Matrix::translation(3.0 * index as f64, 0.0, 0.0))
+                    .unwrap();
+                Box::new(sphere) as Box<dyn Shape<f64>>
+            })
+            .collect()
+    }
+
+    #[test]
+    fn build_empty_hierarchy() {
+        let bvh = Bvh::<f64>::build(&[]);
+        let stats = bvh.stats();
+        let ray = Ray::new(
+            Tuple::new_point(0.0, 0.0, -5.0),
+            Tuple::new_vector(0.0, 0.0, 1.0),
+        );
+
+        assert_eq!(stats.nodes, 0);
+        assert_eq!(stats.leaves, 0);
+        assert!(bvh.intersect(&[], ray).is_empty())
+    }
+
+    #[test]
+    fn few_objects_are_a_single_leaf() {
+        let objects = sphere_row(3);
+        let stats = Bvh::build(&objects).stats();
+
+        assert_eq!(stats.nodes, 1);
+        assert_eq!(stats.leaves, 1);
+        assert_eq!(stats.depth, 1)
+    }
+
+    #[test]
+    fn many_objects_are_split() {
+        let objects = sphere_row(64);
+        let stats = Bvh::build(&objects).stats();
+
+        // a binary tree always has one leaf more than interior nodes
+        assert_eq!(stats.nodes, 2 * stats.leaves - 1);
+        assert!(stats.leaves >= 64 / 4);
+        assert!(stats.depth > 1);
+        assert_eq!(stats.unbounded, 0)
+    }
+
+    #[test]
+    fn planes_are_kept_out_of_the_tree() {
+        let mut objects = sphere_row(8);
+        objects.push(Box::new(Plane::new(100)));
+        let bvh = Bvh::build(&objects);
+        let ray = Ray::new(
+            Tuple::new_point(0.0, 5.0, 0.0),
+            Tuple::new_vector(0.0, -1.0, 0.0),
+        );
+        let intersections = bvh.intersect(&objects, ray);
+
+        assert_eq!(bvh.stats().unbounded, 1);
+        assert_eq!(intersections.hit().unwrap().object.id(), 0)
+    }
+
+    #[test]
+    fn hierarchy_finds_same_hit_as_every_object() {
+        let objects = sphere_row(50);
+        let bvh = Bvh::build(&objects);
+        let parameters = [
+            ((-5.0, 0.0, 0.0), (1.0, 0.0, 0.0)),
+            ((155.0, 0.0, 0.0), (-1.0, 0.0, 0.0)),
+            ((60.5, 0.0, -5.0), (0.0, 0.0, 1.0)),
+            ((30.0, 10.0, 0.0), (0.0, -1.0, 0.0)),
+            ((0.0, 0.0, -10.0), (1.0, 0.0, 0.3)),
+        ];
+
+        for ((ox, oy, oz), (dx, dy, dz)) in parameters {
+            let direction = Tuple::new_vector(dx, dy, dz).normalize();
+            let ray = Ray::new(Tuple::new_point(ox, oy, oz), direction);
+            let mut expected = objects[0].intersect(ray);
+            objects[1..]
+                .iter()
+                .for_each(|object| expected.merge(object.intersect(ray)));
+
+            let hit = bvh.intersect(&objects, ray).hit();
+            match (hit, expected.hit()) {
+                (Some(hit), Some(expected)) => assert_eq!(hit, expected),
+                (hit, expected) => assert_eq!(hit.is_none(), expected.is_none()),
+            }
+        }
+    }
+
+    #[test]
+    fn ray_missing_everything_has_no_hit() {
+        let objects = sphere_row(20);
+        let bvh = Bvh::build(&objects);
+        let ray = Ray::new(
+            Tuple::new_point(0.0, 5.0, -5.0),
+            Tuple::new_vector(1.0, 0.0, 0.0),
+        );
+
+        assert!(bvh.intersect(&objects, ray).is_empty());
+        assert!(!bvh.any_hit(&objects, ray, f64::INFINITY))
+    }
+
+    #[test]
+    fn any_hit_respects_distance() {
+        let objects = sphere_row(20);
+        let bvh = Bvh::build(&objects);
+        let ray = Ray::new(
+            Tuple::new_point(30.0, 0.0, -5.0),
+            Tuple::new_vector(0.0, 0.0, 1.0),
+        );
+
+        assert!(bvh.any_hit(&objects, ray, 10.0));
+        assert!(!bvh.any_hit(&objects, ray, 3.0))
+    }
+}
@@ -0,0 +1,256 @@
+use crate::canvas::Canvas;
+use crate::color::Color;
+use crate::ray::Ray;
+use crate::world::World;
+use crate::{Matrix, Tuple};
+use num::Float;
+use rayon::prelude::*;
+use std::ops::AddAssign;
+
+/*
+    This file contains the camera struct. The camera maps the pixels of
+    a canvas onto rays that are cast into the world
+*/
+
+#[derive(Debug, Clone)]
+pub struct Camera<T>
+where
+    T: Float,
+{
+    hsize: usize,
+    vsize: usize,
+    field_of_view: T,
+    transformation: Matrix<T, 4>,
+    inverse: Matrix<T, 4>,
+    half_width: T,
+    half_height: T,
+    pixel_size: T,
+}
+
+impl<T> Camera<T>
+where
+    T: Float,
+    T: AddAssign,
+{
+    // create a camera with a canvas of hsize by vsize pixels, the camera
+    // looks from the origin towards negative z until it is transformed
+    pub fn new(hsize: usize, vsize: usize, field_of_view: T) -> Self {
+        let mut camera = Self {
+            hsize,
+            vsize,
+            field_of_view,
+            transformation: Matrix::identity_matrix(),
+            inverse: Matrix::identity_matrix(),
+            half_width: T::zero(),
+            half_height: T::zero(),
+            pixel_size: T::zero(),
+        };
+        camera.update_pixel_size();
+        camera
+    }
+
+    // compute the size of the canvas and of a pixel again, this
+    // has to happen whenever the size or the field of view changes
+    fn update_pixel_size(&mut self) {
+        // the canvas is one unit in front of the camera, half the
+        // view covers the distance from the center to the edge
+        let half_view = (self.field_of_view / T::from(2.0).unwrap()).tan();
+        let aspect = T::from(self.hsize).unwrap() / T::from(self.vsize).unwrap();
+        let (half_width, half_height) = match aspect >= T::one() {
+            true => (half_view, half_view / aspect),
+            false => (half_view * aspect, half_view),
+        };
+        self.half_width = half_width;
+        self.half_height = half_height;
+        self.pixel_size = (half_width * T::from(2.0).unwrap()) / T::from(self.hsize).unwrap()
+    }
+
+    pub fn hsize(&self) -> usize {
+        self.hsize
+    }
+
+    pub fn set_hsize(&mut self, hsize: usize) {
+        self.hsize = hsize;
+        self.update_pixel_size()
+    }
+
+    pub fn vsize(&self) -> usize {
+        self.vsize
+    }
+
+    pub fn set_vsize(&mut self, vsize: usize) {
+        self.vsize = vsize;
+        self.update_pixel_size()
+    }
+
+    pub fn field_of_view(&self) -> T {
+        self.field_of_view
+    }
+
+    pub fn set_field_of_view(&mut self, field_of_view: T) {
+        self.field_of_view = field_of_view;
+        self.update_pixel_size()
+    }
+
+    pub fn transformation(&self) -> &Matrix<T, 4> {
+        &self.transformation
+    }
+
+    // change the transformation of the camera, usually created with
+    // Matrix::view_transform. fails when it can not be inverted
+    pub fn set_transformation(&mut self, transformation: Matrix<T, 4>) -> Result<(), &'static str> {
+        match transformation.inverse() {
+            Ok(inverse) => {
+                self.transformation = transformation;
+                self.inverse = inverse;
+                Ok(())
+            }
+            Err(_) => Err("transformation is not invertible"),
+        }
+    }
+
+    pub fn pixel_size(&self) -> T {
+        self.pixel_size
+    }
+
+    // return the ray that starts at the camera and passes
+    // through the center of the given pixel
+    pub fn ray_for_pixel(&self, px: usize, py: usize) -> Ray<T> {
+        let half = T::from(0.5).unwrap();
+        let x_offset = (T::from(px).unwrap() + half) * self.pixel_size;
+        let y_offset = (T::from(py).unwrap() + half) * self.pixel_size;
+
+        // the camera looks towards negative z, so positive x is to the left
+        let world_x = self.half_width - x_offset;
+        let world_y = self.half_height - y_offset;
+
+        let pixel = self.inverse.clone() * Tuple::new_point(world_x, world_y, -T::one());
+        let origin = self.inverse.clone() * Tuple::new_point(T::zero(), T::zero(), T::zero());
+
+        Ray::new(origin, (pixel - origin).normalize())
+    }
+
+    // render the world to a canvas, the rows of the
+    // canvas are rendered in parallel
+    pub fn render(&self, world: &World<T>) -> Canvas
+    where
+        T: Send + Sync,
+    {
+        let rows: Vec<Vec<Color>> = (0..self.vsize)
+            .into_par_iter()
+            .map(|y| {
+                (0..self.hsize)
+                    .map(|x| world.color_at(self.ray_for_pixel(x, y)))
+                    .collect()
+            })
+            .collect();
+
+        let mut canvas = Canvas::new(self.hsize, self.vsize);
+        for (y, row) in rows.into_iter().enumerate() {
+            for (x, color) in row.into_iter().enumerate() {
+                let _ = canvas.write_pixel(x, y, color);
+            }
+        }
+        canvas
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::camera::Camera;
+    use crate::color::Color;
+    use crate::world::World;
+    use crate::{Matrix, Tuple};
+    use std::f64::consts::{FRAC_1_SQRT_2, PI};
+
+    #[test]
+    fn create_camera() {
+        let camera = Camera::<f64>::new(160, 120, PI / 2.0);
+
+        assert_eq!(camera.hsize(), 160);
+        assert_eq!(camera.vsize(), 120);
+        assert_eq!(camera.field_of_view(), PI / 2.0);
+        assert_eq!(*camera.transformation(), Matrix::identity_matrix())
+    }
+
+    #[test]
+    fn pixel_size_horizontal_canvas() {
+        let camera = Camera::<f64>::new(200, 125, PI / 2.0);
+
+        assert!((camera.pixel_size() - 0.01).abs() < 0.00001)
+    }
+
+    #[test]
+    fn pixel_size_vertical_canvas() {
+        let camera = Camera::<f64>::new(125, 200, PI / 2.0);
+
+        assert!((camera.pixel_size() - 0.01).abs() < 0.00001)
+    }
+
+    #[test]
+    fn changing_size_updates_pixel_size() {
+        let mut camera = Camera::<f64>::new(10, 10, 1.0);
+        camera.set_hsize(100);
+        camera.set_vsize(50);
+        camera.set_field_of_view(PI / 2.0);
+        let ray = camera.ray_for_pixel(99, 25);
+
+        assert!((camera.pixel_size() - 0.02).abs() < 0.00001);
+        assert_eq!(
+            ray.direction,
+            Tuple::new_vector(-0.99, -0.01, -1.0).normalize()
+        )
+    }
+
+    #[test]
+    fn ray_through_center_of_canvas() {
+        let camera = Camera::<f64>::new(201, 101, PI / 2.0);
+        let ray = camera.ray_for_pixel(100, 50);
+
+        assert_eq!(ray.origin, Tuple::new_point(0.0, 0.0, 0.0));
+        assert_eq!(ray.direction, Tuple::new_vector(0.0, 0.0, -1.0))
+    }
+
+    #[test]
+    fn ray_through_corner_of_canvas() {
+        let camera = Camera::<f64>::new(201, 101, PI / 2.0);
+        let ray = camera.ray_for_pixel(0, 0);
+
+        assert_eq!(ray.origin, Tuple::new_point(0.0, 0.0, 0.0));
+        assert_eq!(ray.direction, Tuple::new_vector(0.66519, 0.33259, -0.66851))
+    }
+
+    #[test]
+    fn ray_when_camera_is_transformed() {
+        let mut camera = Camera::<f64>::new(201, 101, PI / 2.0);
+        camera
+            .set_transformation(Matrix::rotate_y(PI / 4.0) * Matrix::translation(0.0, -2.0, 5.0))
+            .unwrap();
+        let ray = camera.ray_for_pixel(100, 50);
+
+        assert_eq!(ray.origin, Tuple::new_point(0.0, 2.0, -5.0));
+        assert_eq!(
+            ray.direction,
+            Tuple::new_vector(FRAC_1_SQRT_2, 0.0, -FRAC_1_SQRT_2)
+        )
+    }
+
+    #[test]
+    fn render_world_with_camera() {
+        let world = World::test_world();
+        let mut camera = Camera::new(11, 11, PI / 2.0);
+        camera
+            .set_transformation(Matrix::view_transform(
+                Tuple::new_point(0.0, 0.0, -5.0),
+                Tuple::new_point(0.0, 0.0, 0.0),
+                Tuple::new_vector(0.0, 1.0, 0.0),
+            ))
+            .unwrap();
+        let canvas = camera.render(&world);
+
+        assert_eq!(
+            *canvas.at_pixel(5, 5),
+            Color::new(0.38066119, 0.47582649, 0.28549589)
+        )
+    }
+}
@@ -76,7 +76,7 @@ impl Canvas {
                 // characters.
                 string_vec.iter().for_each(|c| {
                     match ppm_vec.last().unwrap().len() + c.chars().count() + 3 <= 70 {
-                        true => ppm_vec.last_mut().unwrap().push_str(&*format!("{} ", c)),
+                        true => ppm_vec.last_mut().unwrap().push_str(&format!("{} ", c)),
                         false => {
                             ppm_vec.last_mut().unwrap().push('\n');
                             ppm_vec.push(format!("{} ", c))
@@ -53,21 +53,9 @@ impl Sub<Color> for Color {
 // all values that are below 0 or above 255 to be 0 or 255
 impl Display for Color {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        let red = match (self.red * 255.0).ceil() as i32 {
-            n if n > 255 => 255,
-            n if n < 0 => 0,
-            n => n,
-        };
-        let green = match (self.green * 255.0).ceil() as i32 {
-            n if n > 255 => 255,
-            n if n < 0 => 0,
-            n => n,
-        };
-        let blue = match (self.blue * 255.0).ceil() as i32 {
-            n if n > 255 => 255,
-            n if n < 0 => 0,
-            n => n,
-        };
+        let red = ((self.red * 255.0).ceil() as i32).clamp(0, 255);
+        let green = ((self.green * 255.0).ceil() as i32).clamp(0, 255);
+        let blue = ((self.blue * 255.0).ceil() as i32).clamp(0, 255);
         write!(f, "{} {} {}", red, green, blue)
     }
 }
@@ -103,21 +91,9 @@ impl Color {
 
     // return a vec of all the color values
     pub fn to_vec_string(&self) -> Vec<String> {
-        let red = match (self.red * 255.0).ceil() as i32 {
-            n if n > 255 => 255,
-            n if n < 0 => 0,
-            n => n,
-        };
-        let green = match (self.green * 255.0).ceil() as i32 {
-            n if n > 255 => 255,
-            n if n < 0 => 0,
-            n => n,
-        };
-        let blue = match (self.blue * 255.0).ceil() as i32 {
-            n if n > 255 => 255,
-            n if n < 0 => 0,
-            n => n,
-        };
+        let red = ((self.red * 255.0).ceil() as i32).clamp(0, 255);
+        let green = ((self.green * 255.0).ceil() as i32).clamp(0, 255);
+        let blue = ((self.blue * 255.0).ceil() as i32).clamp(0, 255);
         vec![red.to_string(), green.to_string(), blue.to_string()]
     }
 }
@@ -0,0 +1,302 @@
+use crate::bounds::Aabb;
+use crate::float_service::EPSILON;
+use crate::intersection::{Intersection, Intersections};
+use crate::ray::Ray;
+use crate::shape::{Shape, ShapeProperties};
+use crate::Tuple;
+use num::Float;
+use std::ops::AddAssign;
+
+// double napped cone around the y axis in object space, the radius at
+// every height is equal to the absolute value of y. it can be truncated
+// between minimum and maximum and closed with end caps
+#[derive(Clone, PartialEq, Debug)]
+pub struct Cone<T>
+where
+    T: Float,
+{
+    properties: ShapeProperties<T>,
+    pub minimum: T,
+    pub maximum: T,
+    pub closed: bool,
+}
+
+impl<T> Cone<T>
+where
+    T: Float,
+    T: AddAssign,
+{
+    // create an infinite cone without caps
+    pub fn new(id: i32) -> Self {
+        Self::new_truncated(id, T::neg_infinity(), T::infinity(), false)
+    }
+
+    // create a cone that only exists between minimum and maximum,
+    // the bounds themselves are excluded
+    pub fn new_truncated(id: i32, minimum: T, maximum: T, closed: bool) -> Self {
+        Self {
+            properties: ShapeProperties::new(id),
+            minimum,
+            maximum,
+            closed,
+        }
+    }
+}
+
+// check if the intersection at value is within the radius
+// of the cone on the end cap at height y
+fn check_cap<T>(ray: &Ray<T>, value: T, y: T) -> bool
+where
+    T: Float,
+    T: AddAssign,
+{
+    let x = ray.origin.x + value * ray.direction.x;
+    let z = ray.origin.z + value * ray.direction.z;
+
+    x.powi(2) + z.powi(2) <= y.powi(2)
+}
+
+impl<T> Cone<T>
+where
+    T: Float,
+    T: AddAssign,
+    T: Send + Sync + 'static,
+{
+    // add the intersection with the wall when it lies between the bounds
+    fn push_within_bounds<'a>(
+        &'a self,
+        ray: &Ray<T>,
+        value: T,
+        intersections: &mut Vec<Intersection<'a, T>>,
+    ) {
+        let y = ray.origin.y + value * ray.direction.y;
+        if self.minimum < y && y < self.maximum {
+            intersections.push(Intersection::new(value, self))
+        }
+    }
+
+    // add the intersections of the ray with the end caps, a ray that
+    // moves parallel to the caps can not hit them. like for the cylinder
+    // only an exactly zero direction counts as parallel
+    fn intersect_caps<'a>(&'a self, ray: &Ray<T>, intersections: &mut Vec<Intersection<'a, T>>) {
+        if !self.closed || ray.direction.y == T::zero() {
+            return;
+        }
+
+        for cap in [self.minimum, self.maximum] {
+            let value = (cap - ray.origin.y) / ray.direction.y;
+            if check_cap(ray, value, cap) {
+                intersections.push(Intersection::new(value, self))
+            }
+        }
+    }
+}
+
+impl<T> Shape<T> for Cone<T>
+where
+    T: Float,
+    T: AddAssign,
+    T: Send + Sync + 'static,
+{
+    fn properties(&self) -> &ShapeProperties<T> {
+        &self.properties
+    }
+
+    fn properties_mut(&mut self) -> &mut ShapeProperties<T> {
+        &mut self.properties
+    }
+
+    fn local_intersect(&self, ray: Ray<T>) -> Intersections<'_, T> {
+        let mut intersections = vec![];
+        let two = T::from(2.0).unwrap();
+        let (origin, direction) = (ray.origin, ray.direction);
+        // a grows with the square of the direction, which gets shorter as
+        // the cone gets bigger, so the tolerance is scaled the same way
+        let epsilon = T::from(EPSILON).unwrap() * direction.dot_product(&direction);
+
+        let a = direction.x.powi(2) - direction.y.powi(2) + direction.z.powi(2);
+        let b = two * origin.x * direction.x - two * origin.y * direction.y
+            + two * origin.z * direction.z;
+        let c = origin.x.powi(2) - origin.y.powi(2) + origin.z.powi(2);
+
+        if a.abs() < epsilon {
+            // the ray is parallel to one of the halves of the cone, so
+            // it can only cross the other half once. when b is zero as
+            // well the ray misses the walls completely
+            if b != T::zero() {
+                self.push_within_bounds(&ray, -c / (two * b), &mut intersections);
+            }
+        } else {
+            let discriminant = b.powi(2) - T::from(4.0).unwrap() * a * c;
+            if discriminant < T::zero() {
+                return Intersections::empty();
+            }
+
+            for value in [
+                (-b - discriminant.sqrt()) / (two * a),
+                (-b + discriminant.sqrt()) / (two * a),
+            ] {
+                self.push_within_bounds(&ray, value, &mut intersections);
+            }
+        }
+
+        self.intersect_caps(&ray, &mut intersections);
+        Intersections::new(intersections)
+    }
+
+    // points within the radius at the bounds are on the caps,
+    // all other points are on the wall of the cone
+    fn local_normal_at(&self, point: Tuple<T>, _hit: &Intersection<'_, T>) -> Tuple<T> {
+        let distance = point.x.powi(2) + point.z.powi(2);
+        let epsilon = T::from(EPSILON).unwrap();
+
+        if distance < self.maximum.powi(2) && point.y >= self.maximum - epsilon {
+            Tuple::new_vector(T::zero(), T::one(), T::zero())
+        } else if distance < self.minimum.powi(2) && point.y <= self.minimum + epsilon {
+            Tuple::new_vector(T::zero(), -T::one(), T::zero())
+        } else {
+            let y = match point.y > T::zero() {
+                true => -distance.sqrt(),
+                false => distance.sqrt(),
+            };
+            Tuple::new_vector(point.x, y, point.z)
+        }
+    }
+
+    fn bounds(&self) -> Aabb<T> {
+        // the radius is largest at the bound furthest from the tip
+        let radius = self.minimum.abs().max(self.maximum.abs());
+        Aabb::new(
+            Tuple::new_point(-radius, self.minimum, -radius),
+            Tuple::new_point(radius, self.maximum, radius),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cone::Cone;
+    use crate::intersection::Intersection;
+    use crate::ray::Ray;
+    use crate::shape::Shape;
+    use crate::{Matrix, Tuple};
+    use std::f64::consts::SQRT_2;
+
+    #[test]
+    fn ray_hits_cone() {
+        let cone = Cone::<f64>::new(1);
+        let parameters = [
+            ((0.0, 0.0, -5.0), (0.0, 0.0, 1.0), 5.0, 5.0),
+            ((0.0, 0.0, -5.0), (1.0, 1.0, 1.0), 8.66025, 8.66025),
+            ((1.0, 1.0, -5.0), (-0.5, -1.0, 1.0), 4.55006, 49.44994),
+        ];
+
+        for ((ox, oy, oz), (dx, dy, dz), t1, t2) in parameters {
+            let direction = Tuple::new_vector(dx, dy, dz).normalize();
+            let ray = Ray::new(Tuple::new_point(ox, oy, oz), direction);
+            let inter = cone.local_intersect(ray);
+
+            assert_eq!(inter.len(), 2);
+            assert!((inter[0].value - t1).abs() < 0.0001);
+            assert!((inter[1].value - t2).abs() < 0.0001)
+        }
+    }
+
+    #[test]
+    fn ray_parallel_to_one_half_of_cone() {
+        let cone = Cone::<f64>::new(1);
+        let direction = Tuple::new_vector(0.0, 1.0, 1.0).normalize();
+        let ray = Ray::new(Tuple::new_point(0.0, 0.0, -1.0), direction);
+        let inter = cone.local_intersect(ray);
+
+        assert_eq!(inter.len(), 1);
+        assert!((inter[0].value - 0.35355).abs() < 0.0001)
+    }
+
+    #[test]
+    fn intersect_caps_of_closed_cone() {
+        let cone = Cone::<f64>::new_truncated(1, -0.5, 0.5, true);
+        let parameters = [
+            ((0.0, 0.0, -5.0), (0.0, 1.0, 0.0), 0),
+            ((0.0, 0.0, -0.25), (0.0, 1.0, 1.0), 2),
+            ((0.0, 0.0, -0.25), (0.0, 1.0, 0.0), 4),
+        ];
+
+        for ((ox, oy, oz), (dx, dy, dz), count) in parameters {
+            let direction = Tuple::new_vector(dx, dy, dz).normalize();
+            let ray = Ray::new(Tuple::new_point(ox, oy, oz), direction);
+
+            assert_eq!(cone.local_intersect(ray).len(), count)
+        }
+    }
+
+    #[test]
+    fn normal_on_cone() {
+        let cone = Cone::<f64>::new(1);
+        let parameters = [
+            ((0.0, 0.0, 0.0), (0.0, 0.0, 0.0)),
+            ((1.0, 1.0, 1.0), (1.0, -SQRT_2, 1.0)),
+            ((-1.0, -1.0, 0.0), (-1.0, 1.0, 0.0)),
+        ];
+
+        for ((px, py, pz), (nx, ny, nz)) in parameters {
+            let normal =
+                cone.local_normal_at(Tuple::new_point(px, py, pz), &Intersection::new(0.0, &cone));
+
+            assert_eq!(normal, Tuple::new_vector(nx, ny, nz))
+        }
+    }
+
+    #[test]
+    fn normal_on_cone_caps() {
+        let cone = Cone::<f64>::new_truncated(1, -1.0, 2.0, true);
+
+        assert_eq!(
+            cone.local_normal_at(
+                Tuple::new_point(0.5, 2.0, 0.0),
+                &Intersection::new(0.0, &cone)
+            ),
+            Tuple::new_vector(0.0, 1.0, 0.0)
+        );
+        assert_eq!(
+            cone.local_normal_at(
+                Tuple::new_point(0.0, -1.0, 0.5),
+                &Intersection::new(0.0, &cone)
+            ),
+            Tuple::new_vector(0.0, -1.0, 0.0)
+        )
+    }
+
+    #[test]
+    fn bounds_of_truncated_cone() {
+        let bounds = Cone::<f64>::new_truncated(1, -5.0, 3.0, false).bounds();
+
+        assert_eq!(bounds.min, Tuple::new_point(-5.0, -5.0, -5.0));
+        assert_eq!(bounds.max, Tuple::new_point(5.0, 3.0, 5.0))
+    }
+
+    #[test]
+    fn intersect_large_scaled_cone() {
+        let mut cone = Cone::<f64>::new_truncated(1, -1.0, 1.0, true);
+        cone.set_transformation(Matrix::scaling(1000.0, 1000.0, 1000.0))
+            .unwrap();
+        let parameters = [
+            ((-5000.0, 500.0, 0.0), (1.0, 0.0, 0.0), vec![4500.0, 5500.0]),
+            (
+                (0.0, -5000.0, 500.0),
+                (0.0, 1.0, 0.0),
+                vec![4000.0, 4500.0, 5500.0, 6000.0],
+            ),
+        ];
+
+        for ((ox, oy, oz), (dx, dy, dz), values) in parameters {
+            let ray = Ray::new(Tuple::new_point(ox, oy, oz), Tuple::new_vector(dx, dy, dz));
+            let inter = cone.intersect(ray);
+
+            assert_eq!(inter.len(), values.len());
+            for (inter, value) in inter.iter().zip(values) {
+                assert!((inter.value - value).abs() < 1e-6)
+            }
+        }
+    }
+}
@@ -0,0 +1,413 @@
+use crate::bounds::Aabb;
+use crate::intersection::{Intersection, Intersections};
+use crate::ray::Ray;
+use crate::shape::{Shape, ShapeProperties};
+use crate::Tuple;
+use num::Float;
+use std::ops::AddAssign;
+
+/*
+    This file contains the constructive solid geometry shape. It combines
+    two shapes by only keeping the intersections that lie on the surface
+    of the combined shape
+*/
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CsgOperation {
+    // everything that is in either shape
+    Union,
+    // only what is in both shapes
+    Intersection,
+    // what is in the left shape but not in the right shape
+    Difference,
+}
+
+impl CsgOperation {
+    // decide if a hit is on the surface of the combined shape. left_hit is
+    // true when the left shape was hit, in_left and in_right tell if the
+    // hit is inside of the left and right shape
+    pub fn intersection_allowed(&self, left_hit: bool, in_left: bool, in_right: bool) -> bool {
+        match self {
+            CsgOperation::Union => (left_hit && !in_right) || (!left_hit && !in_left),
+            CsgOperation::Intersection => (left_hit && in_right) || (!left_hit && in_left),
+            CsgOperation::Difference => (left_hit && !in_right) || (!left_hit && in_left),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct Csg<T>
+where
+    T: Float,
+    T: AddAssign,
+{
+    properties: ShapeProperties<T>,
+    operation: CsgOperation,
+    left: Box<dyn Shape<T>>,
+    right: Box<dyn Shape<T>>,
+    bounds: Aabb<T>,
+}
+
+impl<T> Csg<T>
+where
+    T: Float,
+    T: AddAssign,
+    T: Send + Sync + 'static,
+{
+    // combine the left and the right shape, they are
+    // transformed together with the csg shape
+    pub fn new(
+        id: i32,
+        operation: CsgOperation,
+        left: Box<dyn Shape<T>>,
+        right: Box<dyn Shape<T>>,
+    ) -> Self {
+        let bounds = left
+            .parent_space_bounds()
+            .union(&right.parent_space_bounds());
+        let mut csg = Self {
+            properties: ShapeProperties::new(id),
+            operation,
+            left,
+            right,
+            bounds,
+        };
+        csg.update_children();
+        csg
+    }
+
+    pub fn operation(&self) -> CsgOperation {
+        self.operation
+    }
+
+    pub fn left(&self) -> &dyn Shape<T> {
+        self.left.as_ref()
+    }
+
+    pub fn right(&self) -> &dyn Shape<T> {
+        self.right.as_ref()
+    }
+
+    // walk through the sorted intersections and keep track of whether the
+    // ray is inside of the left and right shape, only the intersections
+    // allowed by the operation are kept
+    pub fn filter_intersections<'a>(
+        &self,
+        intersections: Intersections<'a, T>,
+    ) -> Intersections<'a, T> {
+        let mut in_left = false;
+        let mut in_right = false;
+        let mut result = vec![];
+
+        for intersection in intersections {
+            let left_hit = self.left.includes(intersection.object);
+            if self
+                .operation
+                .intersection_allowed(left_hit, in_left, in_right)
+            {
+                result.push(intersection)
+            }
+
+            match left_hit {
+                true => in_left = !in_left,
+                false => in_right = !in_right,
+            }
+        }
+        Intersections::new(result)
+    }
+}
+
+impl<T> Shape<T> for Csg<T>
+where
+    T: Float,
+    T: AddAssign,
+    T: Send + Sync + 'static,
+{
+    fn properties(&self) -> &ShapeProperties<T> {
+        &self.properties
+    }
+
+    fn properties_mut(&mut self) -> &mut ShapeProperties<T> {
+        &mut self.properties
+    }
+
+    // the children are skipped when the ray misses the box around them
+    fn local_intersect(&self, ray: Ray<T>) -> Intersections<'_, T> {
+        if !self.bounds.intersects(&ray) {
+            return Intersections::empty();
+        }
+        let mut intersections = self.left.intersect(ray);
+        intersections.merge(self.right.intersect(ray));
+        self.filter_intersections(intersections)
+    }
+
+    // the hits of the children are needed to know which hits are on the
+    // surface, so only the box around the children can end the test early
+    fn local_any_hit(&self, ray: Ray<T>, max_t: T) -> bool {
+        match self.bounds.intersection_range(&ray) {
+            Some((tmin, tmax)) if tmin < max_t && tmax > T::zero() => {}
+            _ => return false,
+        }
+        let mut intersections = self.left.intersect(ray);
+        intersections.merge(self.right.intersect(ray));
+        self.filter_intersections(intersections)
+            .iter()
+            .any(|intersection| intersection.value > T::zero() && intersection.value < max_t)
+    }
+
+    // the intersections of a csg shape always reference its children,
+    // so the normal of the csg shape itself is never needed
+    fn local_normal_at(&self, _point: Tuple<T>, _hit: &Intersection<'_, T>) -> Tuple<T> {
+        unreachable!(
+            "a csg shape is never hit itself, the normal is taken from the child that was hit"
+        )
+    }
+
+    fn bounds(&self) -> Aabb<T> {
+        self.bounds
+    }
+
+    fn update_children(&mut self) {
+        let parent_inverse = self.inverse().clone() * self.parent_inverse().clone();
+        self.left.set_parent_inverse(parent_inverse.clone());
+        self.right.set_parent_inverse(parent_inverse)
+    }
+
+    fn includes(&self, other: &dyn Shape<T>) -> bool {
+        std::ptr::addr_eq(self, other) || self.left.includes(other) || self.right.includes(other)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::csg::{Csg, CsgOperation};
+    use crate::cube::Cube;
+    use crate::intersection::{Intersection, Intersections};
+    use crate::ray::Ray;
+    use crate::shape::{same_shape, Shape};
+    use crate::sphere::Sphere;
+    use crate::{Matrix, Tuple};
+
+    #[test]
+    fn create_csg() {
+        let csg = Csg::<f64>::new(
+            1,
+            CsgOperation::Union,
+            Box::new(Sphere::new(2)),
+            Box::new(Cube::new(3)),
+        );
+
+        assert_eq!(csg.operation(), CsgOperation::Union);
+        assert_eq!(csg.left().id(), 2);
+        assert_eq!(csg.right().id(), 3)
+    }
+
+    #[test]
+    fn evaluate_rules_for_operations() {
+        let parameters = [
+            (CsgOperation::Union, true, true, true, false),
+            (CsgOperation::Union, true, true, false, true),
+            (CsgOperation::Union, true, false, true, false),
+            (CsgOperation::Union, true, false, false, true),
+            (CsgOperation::Union, false, true, true, false),
+            (CsgOperation::Union, false, true, false, false),
+            (CsgOperation::Union, false, false, true, true),
+            (CsgOperation::Union, false, false, false, true),
+            (CsgOperation::Intersection, true, true, true, true),
+            (CsgOperation::Intersection, true, true, false, false),
+            (CsgOperation::Intersection, true, false, true, true),
+            (CsgOperation::Intersection, true, false, false, false),
+            (CsgOperation::Intersection, false, true, true, true),
+            (CsgOperation::Intersection, false, true, false, true),
+            (CsgOperation::Intersection, false, false, true, false),
+            (CsgOperation::Intersection, false, false, false, false),
+            (CsgOperation::Difference, true, true, true, false),
+            (CsgOperation::Difference, true, true, false, true),
+            (CsgOperation::Difference, true, false, true, false),
+            (CsgOperation::Difference, true, false, false, true),
+            (CsgOperation::Difference, false, true, true, true),
+            (CsgOperation::Difference, false, true, false, true),
+            (CsgOperation::Difference, false, false, true, false),
+            (CsgOperation::Difference, false, false, false, false),
+        ];
+
+        for (operation, left_hit, in_left, in_right, allowed) in parameters {
+            assert_eq!(
+                operation.intersection_allowed(left_hit, in_left, in_right),
+                allowed
+            )
+        }
+    }
+
+    #[test]
+    fn filter_list_of_intersections() {
+        let parameters = [
+            (CsgOperation::Union, 0, 3),
+            (CsgOperation::Intersection, 1, 2),
+            (CsgOperation::Difference, 0, 1),
+        ];
+
+        for (operation, first, second) in parameters {
+            let csg = Csg::<f64>::new(
+                1,
+                operation,
+                Box::new(Sphere::new(2)),
+                Box::new(Cube::new(3)),
+            );
+            let (left, right) = (csg.left(), csg.right());
+            let all = [
+                Intersection::new(1.0, left),
+                Intersection::new(2.0, right),
+                Intersection::new(3.0, left),
+                Intersection::new(4.0, right),
+            ];
+            let result = csg.filter_intersections(Intersections::new(all.to_vec()));
+
+            assert_eq!(result.len(), 2);
+            assert_eq!(result[0], all[first]);
+            assert_eq!(result[1], all[second])
+        }
+    }
+
+    #[test]
+    fn ray_misses_csg() {
+        let csg = Csg::<f64>::new(
+            1,
+            CsgOperation::Union,
+            Box::new(Sphere::new(2)),
+            Box::new(Cube::new(3)),
+        );
+        let ray = Ray::new(
+            Tuple::new_point(0.0, 2.0, -5.0),
+            Tuple::new_vector(0.0, 0.0, 1.0),
+        );
+
+        assert!(csg.local_intersect(ray).is_empty())
+    }
+
+    #[test]
+    fn ray_hits_csg() {
+        let mut right = Sphere::new(3);
+        right
+            .set_transformation(Matrix::translation(0.0, 0.0, 0.5))
+            .unwrap();
+        let csg = Csg::<f64>::new(
+            1,
+            CsgOperation::Union,
+            Box::new(Sphere::new(2)),
+            Box::new(right),
+        );
+        let ray = Ray::new(
+            Tuple::new_point(0.0, 0.0, -5.0),
+            Tuple::new_vector(0.0, 0.0, 1.0),
+        );
+        let inter = csg.local_intersect(ray);
+
+        assert_eq!(inter.len(), 2);
+        assert_eq!(inter[0].value, 4.0);
+        assert!(same_shape(inter[0].object, csg.left()));
+        assert_eq!(inter[1].value, 6.5);
+        assert!(same_shape(inter[1].object, csg.right()))
+    }
+
+    #[test]
+    fn csg_of_nested_csg_uses_children() {
+        let mut cube = Cube::new(4);
+        cube.set_transformation(Matrix::scaling(0.5, 0.5, 0.5))
+            .unwrap();
+        let inner = Csg::<f64>::new(
+            2,
+            CsgOperation::Union,
+            Box::new(Sphere::new(3)),
+            Box::new(cube),
+        );
+        let mut right = Sphere::new(5);
+        right
+            .set_transformation(Matrix::translation(0.0, 0.0, 0.5))
+            .unwrap();
+        let csg = Csg::new(
+            1,
+            CsgOperation::Difference,
+            Box::new(inner),
+            Box::new(right),
+        );
+        let ray = Ray::new(
+            Tuple::new_point(0.0, 0.0, -5.0),
+            Tuple::new_vector(0.0, 0.0, 1.0),
+        );
+        let inter = csg.local_intersect(ray);
+
+        // the cube is inside of the left sphere so it is not visible,
+        // the right sphere cuts away the back of the left sphere
+        assert_eq!(inter.len(), 2);
+        assert_eq!(inter[0].value, 4.0);
+        assert_eq!(inter[0].object.id(), 3);
+        assert_eq!(inter[1].value, 4.5);
+        assert_eq!(inter[1].object.id(), 5)
+    }
+
+    #[test]
+    fn transformation_of_csg_moves_children() {
+        let mut csg = Csg::<f64>::new(
+            1,
+            CsgOperation::Intersection,
+            Box::new(Sphere::new(2)),
+            Box::new(Cube::new(3)),
+        );
+        csg.set_transformation(Matrix::translation(0.0, 0.0, 10.0))
+            .unwrap();
+        let ray = Ray::new(
+            Tuple::new_point(0.0, 0.0, 0.0),
+            Tuple::new_vector(0.0, 0.0, 1.0),
+        );
+        let hit = csg.intersect(ray).hit().unwrap();
+
+        assert_eq!(hit.value, 9.0);
+        assert_eq!(
+            hit.object.normal_at(ray.position(hit.value), &hit),
+            Tuple::new_vector(0.0, 0.0, -1.0)
+        )
+    }
+
+    #[test]
+    fn csg_bounds_contain_children() {
+        let mut right = Sphere::new(3);
+        right
+            .set_transformation(Matrix::translation(2.0, 3.0, 4.0))
+            .unwrap();
+        let csg = Csg::<f64>::new(
+            1,
+            CsgOperation::Difference,
+            Box::new(Sphere::new(2)),
+            Box::new(right),
+        );
+        let bounds = csg.bounds();
+
+        assert_eq!(bounds.min, Tuple::new_point(-1.0, -1.0, -1.0));
+        assert_eq!(bounds.max, Tuple::new_point(3.0, 4.0, 5.0))
+    }
+
+    #[test]
+    fn any_hit_only_counts_hits_on_surface() {
+        let mut right = Sphere::new(3);
+        right
+            .set_transformation(Matrix::translation(0.0, 0.0, 0.5))
+            .unwrap();
+        let csg = Csg::<f64>::new(
+            1,
+            CsgOperation::Intersection,
+            Box::new(Sphere::new(2)),
+            Box::new(right),
+        );
+        let ray = Ray::new(
+            Tuple::new_point(0.0, 0.0, -5.0),
+            Tuple::new_vector(0.0, 0.0, 1.0),
+        );
+
+        // the left sphere is hit at 4, but only the hit
+        // of the right sphere at 4.5 is on the surface
+        assert_eq!(csg.intersect(ray)[0].value, 4.5);
+        assert!(csg.any_hit(ray, 4.6));
+        assert!(!csg.any_hit(ray, 4.4))
+    }
+}
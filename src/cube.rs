@@ -0,0 +1,247 @@
+use crate::bounds::Aabb;
+use crate::intersection::{Intersection, Intersections};
+use crate::ray::Ray;
+use crate::shape::{Shape, ShapeProperties};
+use crate::Tuple;
+use num::Float;
+use std::ops::AddAssign;
+
+// axis aligned cube that spans -1 to 1 on every axis in object space
+#[derive(Clone, PartialEq, Debug)]
+pub struct Cube<T>
+where
+    T: Float,
+{
+    properties: ShapeProperties<T>,
+}
+
+impl<T> Cube<T>
+where
+    T: Float,
+    T: AddAssign,
+{
+    pub fn new(id: i32) -> Self {
+        Self {
+            properties: ShapeProperties::new(id),
+        }
+    }
+}
+
+// return the values where the ray enters and leaves the slab between
+// min and max on a single axis. any direction that is not exactly zero is
+// divided by, tiny components give huge but correct values. when the ray
+// is parallel to the slab the values are infinite, the sign is picked from
+// the numerator so a ray that starts on the slab does not create a NaN
+pub fn check_axis<T>(origin: T, direction: T, min: T, max: T) -> (T, T)
+where
+    T: Float,
+{
+    let tmin_numerator = min - origin;
+    let tmax_numerator = max - origin;
+
+    let (tmin, tmax) = match direction != T::zero() {
+        true => (tmin_numerator / direction, tmax_numerator / direction),
+        false => (
+            match tmin_numerator > T::zero() {
+                true => T::infinity(),
+                false => T::neg_infinity(),
+            },
+            match tmax_numerator < T::zero() {
+                true => T::neg_infinity(),
+                false => T::infinity(),
+            },
+        ),
+    };
+
+    match tmin > tmax {
+        true => (tmax, tmin),
+        false => (tmin, tmax),
+    }
+}
+
+impl<T> Shape<T> for Cube<T>
+where
+    T: Float,
+    T: AddAssign,
+    T: Send + Sync + 'static,
+{
+    fn properties(&self) -> &ShapeProperties<T> {
+        &self.properties
+    }
+
+    fn properties_mut(&mut self) -> &mut ShapeProperties<T> {
+        &mut self.properties
+    }
+
+    // the ray hits the cube when the largest entering value
+    // is lower then the smallest leaving value of the three slabs
+    fn local_intersect(&self, ray: Ray<T>) -> Intersections<'_, T> {
+        let (xtmin, xtmax) = check_axis(ray.origin.x, ray.direction.x, -T::one(), T::one());
+        let (ytmin, ytmax) = check_axis(ray.origin.y, ray.direction.y, -T::one(), T::one());
+        let (ztmin, ztmax) = check_axis(ray.origin.z, ray.direction.z, -T::one(), T::one());
+
+        let tmin = xtmin.max(ytmin).max(ztmin);
+        let tmax = xtmax.min(ytmax).min(ztmax);
+
+        match tmin > tmax {
+            true => Intersections::empty(),
+            false => Intersections::new(vec![
+                Intersection::new(tmin, self),
+                Intersection::new(tmax, self),
+            ]),
+        }
+    }
+
+    // the face that is hit is the one of the largest component
+    fn local_normal_at(&self, point: Tuple<T>, _hit: &Intersection<'_, T>) -> Tuple<T> {
+        let max = point.x.abs().max(point.y.abs()).max(point.z.abs());
+
+        match max {
+            m if m == point.x.abs() => Tuple::new_vector(point.x, T::zero(), T::zero()),
+            m if m == point.y.abs() => Tuple::new_vector(T::zero(), point.y, T::zero()),
+            _ => Tuple::new_vector(T::zero(), T::zero(), point.z),
+        }
+    }
+
+    fn bounds(&self) -> Aabb<T> {
+        Aabb::new(
+            Tuple::new_point(-T::one(), -T::one(), -T::one()),
+            Tuple::new_point(T::one(), T::one(), T::one()),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cube::Cube;
+    use crate::intersection::Intersection;
+    use crate::ray::Ray;
+    use crate::shape::Shape;
+    use crate::{Matrix, Tuple};
+
+    #[test]
+    fn ray_intersects_cube() {
+        let cube = Cube::<f64>::new(1);
+        let parameters = [
+            ((5.0, 0.5, 0.0), (-1.0, 0.0, 0.0), 4.0, 6.0),
+            ((-5.0, 0.5, 0.0), (1.0, 0.0, 0.0), 4.0, 6.0),
+            ((0.5, 5.0, 0.0), (0.0, -1.0, 0.0), 4.0, 6.0),
+            ((0.5, -5.0, 0.0), (0.0, 1.0, 0.0), 4.0, 6.0),
+            ((0.5, 0.0, 5.0), (0.0, 0.0, -1.0), 4.0, 6.0),
+            ((0.5, 0.0, -5.0), (0.0, 0.0, 1.0), 4.0, 6.0),
+            ((0.0, 0.5, 0.0), (0.0, 0.0, 1.0), -1.0, 1.0),
+        ];
+
+        for ((ox, oy, oz), (dx, dy, dz), t1, t2) in parameters {
+            let ray = Ray::new(Tuple::new_point(ox, oy, oz), Tuple::new_vector(dx, dy, dz));
+            let inter = cube.local_intersect(ray);
+
+            assert_eq!(inter.len(), 2);
+            assert_eq!(inter[0].value, t1);
+            assert_eq!(inter[1].value, t2)
+        }
+    }
+
+    #[test]
+    fn ray_misses_cube() {
+        let cube = Cube::<f64>::new(1);
+        let parameters = [
+            ((-2.0, 0.0, 0.0), (0.2673, 0.5345, 0.8018)),
+            ((0.0, -2.0, 0.0), (0.8018, 0.2673, 0.5345)),
+            ((0.0, 0.0, -2.0), (0.5345, 0.8018, 0.2673)),
+            ((2.0, 0.0, 2.0), (0.0, 0.0, -1.0)),
+            ((0.0, 2.0, 2.0), (0.0, -1.0, 0.0)),
+            ((2.0, 2.0, 0.0), (-1.0, 0.0, 0.0)),
+        ];
+
+        for ((ox, oy, oz), (dx, dy, dz)) in parameters {
+            let ray = Ray::new(Tuple::new_point(ox, oy, oz), Tuple::new_vector(dx, dy, dz));
+
+            assert!(cube.local_intersect(ray).is_empty())
+        }
+    }
+
+    #[test]
+    fn parallel_ray_on_face_has_no_nan() {
+        let cube = Cube::<f64>::new(1);
+        let ray = Ray::new(
+            Tuple::new_point(-1.0, 0.0, -5.0),
+            Tuple::new_vector(0.0, 0.0, 1.0),
+        );
+        let inter = cube.local_intersect(ray);
+
+        assert_eq!(inter.len(), 2);
+        assert_eq!(inter[0].value, 4.0);
+        assert_eq!(inter[1].value, 6.0)
+    }
+
+    #[test]
+    fn normal_on_cube_surface() {
+        let cube = Cube::<f64>::new(1);
+        let parameters = [
+            ((1.0, 0.5, -0.8), (1.0, 0.0, 0.0)),
+            ((-1.0, -0.2, 0.9), (-1.0, 0.0, 0.0)),
+            ((-0.4, 1.0, -0.1), (0.0, 1.0, 0.0)),
+            ((0.3, -1.0, -0.7), (0.0, -1.0, 0.0)),
+            ((-0.6, 0.3, 1.0), (0.0, 0.0, 1.0)),
+            ((0.4, 0.4, -1.0), (0.0, 0.0, -1.0)),
+            ((1.0, 1.0, 1.0), (1.0, 0.0, 0.0)),
+            ((-1.0, -1.0, -1.0), (-1.0, 0.0, 0.0)),
+        ];
+
+        for ((px, py, pz), (nx, ny, nz)) in parameters {
+            let normal =
+                cube.local_normal_at(Tuple::new_point(px, py, pz), &Intersection::new(0.0, &cube));
+
+            assert_eq!(normal, Tuple::new_vector(nx, ny, nz))
+        }
+    }
+
+    #[test]
+    fn intersect_transformed_cube() {
+        let mut cube = Cube::<f64>::new(1);
+        cube.set_transformation(
+            Matrix::translation(0.0, 0.0, 5.0) * Matrix::scaling(2.0, 2.0, 2.0),
+        )
+        .unwrap();
+        let ray = Ray::new(
+            Tuple::new_point(0.0, 0.0, -5.0),
+            Tuple::new_vector(0.0, 0.0, 1.0),
+        );
+        let inter = cube.intersect(ray);
+
+        assert_eq!(inter.len(), 2);
+        assert_eq!(inter[0].value, 8.0);
+        assert_eq!(inter[1].value, 12.0)
+    }
+
+    #[test]
+    fn nearly_parallel_ray_hits_cube() {
+        let cube = Cube::<f64>::new(1);
+        let ray = Ray::new(
+            Tuple::new_point(1.00001, 0.0, -5.0),
+            Tuple::new_vector(-5e-6, 0.0, 1.0),
+        );
+        let inter = cube.local_intersect(ray);
+
+        assert_eq!(inter.len(), 2);
+        assert_eq!(inter[0].value, 4.0);
+        assert_eq!(inter[1].value, 6.0)
+    }
+
+    #[test]
+    fn intersect_large_scaled_cube() {
+        let mut cube = Cube::<f64>::new(1);
+        cube.set_transformation(Matrix::scaling(1e6, 1e6, 1e6))
+            .unwrap();
+        let ray = Ray::new(
+            Tuple::new_point(0.0, 0.0, 0.0),
+            Tuple::new_vector(0.0, 0.0, 1.0),
+        );
+        let inter = cube.intersect(ray);
+
+        assert_eq!(inter.len(), 2);
+        assert!((inter[0].value + 1e6).abs() < 1e-3);
+        assert!((inter[1].value - 1e6).abs() < 1e-3)
+    }
+}
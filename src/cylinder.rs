@@ -0,0 +1,312 @@
+use crate::bounds::Aabb;
+use crate::float_service::EPSILON;
+use crate::intersection::{Intersection, Intersections};
+use crate::ray::Ray;
+use crate::shape::{Shape, ShapeProperties};
+use crate::Tuple;
+use num::Float;
+use std::ops::AddAssign;
+
+// cylinder with radius 1 around the y axis in object space. it can
+// be truncated between minimum and maximum and closed with end caps
+#[derive(Clone, PartialEq, Debug)]
+pub struct Cylinder<T>
+where
+    T: Float,
+{
+    properties: ShapeProperties<T>,
+    pub minimum: T,
+    pub maximum: T,
+    pub closed: bool,
+}
+
+impl<T> Cylinder<T>
+where
+    T: Float,
+    T: AddAssign,
+{
+    // create an infinite cylinder without caps
+    pub fn new(id: i32) -> Self {
+        Self::new_truncated(id, T::neg_infinity(), T::infinity(), false)
+    }
+
+    // create a cylinder that only exists between minimum and maximum,
+    // the bounds themselves are excluded
+    pub fn new_truncated(id: i32, minimum: T, maximum: T, closed: bool) -> Self {
+        Self {
+            properties: ShapeProperties::new(id),
+            minimum,
+            maximum,
+            closed,
+        }
+    }
+}
+
+// check if the intersection at value is within
+// the radius of the cylinder on its end caps
+fn check_cap<T>(ray: &Ray<T>, value: T) -> bool
+where
+    T: Float,
+    T: AddAssign,
+{
+    let x = ray.origin.x + value * ray.direction.x;
+    let z = ray.origin.z + value * ray.direction.z;
+
+    x.powi(2) + z.powi(2) <= T::one()
+}
+
+impl<T> Cylinder<T>
+where
+    T: Float,
+    T: AddAssign,
+    T: Send + Sync + 'static,
+{
+    // add the intersections of the ray with the end caps, a ray that
+    // moves parallel to the caps can not hit them. the ray is in object
+    // space, where it gets shorter as the cylinder gets bigger, so only
+    // an exactly zero direction counts as parallel
+    fn intersect_caps<'a>(&'a self, ray: &Ray<T>, intersections: &mut Vec<Intersection<'a, T>>) {
+        if !self.closed || ray.direction.y == T::zero() {
+            return;
+        }
+
+        for cap in [self.minimum, self.maximum] {
+            let value = (cap - ray.origin.y) / ray.direction.y;
+            if check_cap(ray, value) {
+                intersections.push(Intersection::new(value, self))
+            }
+        }
+    }
+}
+
+impl<T> Shape<T> for Cylinder<T>
+where
+    T: Float,
+    T: AddAssign,
+    T: Send + Sync + 'static,
+{
+    fn properties(&self) -> &ShapeProperties<T> {
+        &self.properties
+    }
+
+    fn properties_mut(&mut self) -> &mut ShapeProperties<T> {
+        &mut self.properties
+    }
+
+    fn local_intersect(&self, ray: Ray<T>) -> Intersections<'_, T> {
+        let mut intersections = vec![];
+        let a = ray.direction.x.powi(2) + ray.direction.z.powi(2);
+
+        // a ray parallel to the y axis can only hit the caps
+        if a != T::zero() {
+            let two = T::from(2.0).unwrap();
+            let b = two * ray.origin.x * ray.direction.x + two * ray.origin.z * ray.direction.z;
+            let c = ray.origin.x.powi(2) + ray.origin.z.powi(2) - T::one();
+            let discriminant = b.powi(2) - T::from(4.0).unwrap() * a * c;
+
+            if discriminant < T::zero() {
+                return Intersections::empty();
+            }
+
+            for value in [
+                (-b - discriminant.sqrt()) / (two * a),
+                (-b + discriminant.sqrt()) / (two * a),
+            ] {
+                let y = ray.origin.y + value * ray.direction.y;
+                if self.minimum < y && y < self.maximum {
+                    intersections.push(Intersection::new(value, self))
+                }
+            }
+        }
+
+        self.intersect_caps(&ray, &mut intersections);
+        Intersections::new(intersections)
+    }
+
+    // points within the radius at the bounds are on the caps,
+    // all other points are on the wall of the cylinder
+    fn local_normal_at(&self, point: Tuple<T>, _hit: &Intersection<'_, T>) -> Tuple<T> {
+        let distance = point.x.powi(2) + point.z.powi(2);
+        let epsilon = T::from(EPSILON).unwrap();
+
+        if distance < T::one() && point.y >= self.maximum - epsilon {
+            Tuple::new_vector(T::zero(), T::one(), T::zero())
+        } else if distance < T::one() && point.y <= self.minimum + epsilon {
+            Tuple::new_vector(T::zero(), -T::one(), T::zero())
+        } else {
+            Tuple::new_vector(point.x, T::zero(), point.z)
+        }
+    }
+
+    fn bounds(&self) -> Aabb<T> {
+        Aabb::new(
+            Tuple::new_point(-T::one(), self.minimum, -T::one()),
+            Tuple::new_point(T::one(), self.maximum, T::one()),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cylinder::Cylinder;
+    use crate::intersection::Intersection;
+    use crate::ray::Ray;
+    use crate::shape::Shape;
+    use crate::{Matrix, Tuple};
+
+    #[test]
+    fn ray_misses_cylinder() {
+        let cylinder = Cylinder::<f64>::new(1);
+        let parameters = [
+            ((1.0, 0.0, 0.0), (0.0, 1.0, 0.0)),
+            ((0.0, 0.0, 0.0), (0.0, 1.0, 0.0)),
+            ((0.0, 0.0, -5.0), (1.0, 1.0, 1.0)),
+        ];
+
+        for ((ox, oy, oz), (dx, dy, dz)) in parameters {
+            let direction = Tuple::new_vector(dx, dy, dz).normalize();
+            let ray = Ray::new(Tuple::new_point(ox, oy, oz), direction);
+
+            assert!(cylinder.local_intersect(ray).is_empty())
+        }
+    }
+
+    #[test]
+    fn ray_hits_cylinder() {
+        let cylinder = Cylinder::<f64>::new(1);
+        let parameters = [
+            ((1.0, 0.0, -5.0), (0.0, 0.0, 1.0), 5.0, 5.0),
+            ((0.0, 0.0, -5.0), (0.0, 0.0, 1.0), 4.0, 6.0),
+            ((0.5, 0.0, -5.0), (0.1, 1.0, 1.0), 6.80798, 7.08872),
+        ];
+
+        for ((ox, oy, oz), (dx, dy, dz), t1, t2) in parameters {
+            let direction = Tuple::new_vector(dx, dy, dz).normalize();
+            let ray = Ray::new(Tuple::new_point(ox, oy, oz), direction);
+            let inter = cylinder.local_intersect(ray);
+
+            assert_eq!(inter.len(), 2);
+            assert!((inter[0].value - t1).abs() < 0.0001);
+            assert!((inter[1].value - t2).abs() < 0.0001)
+        }
+    }
+
+    #[test]
+    fn normal_on_cylinder() {
+        let cylinder = Cylinder::<f64>::new(1);
+        let parameters = [
+            ((1.0, 0.0, 0.0), (1.0, 0.0, 0.0)),
+            ((0.0, 5.0, -1.0), (0.0, 0.0, -1.0)),
+            ((0.0, -2.0, 1.0), (0.0, 0.0, 1.0)),
+            ((-1.0, 1.0, 0.0), (-1.0, 0.0, 0.0)),
+        ];
+
+        for ((px, py, pz), (nx, ny, nz)) in parameters {
+            let normal = cylinder.local_normal_at(
+                Tuple::new_point(px, py, pz),
+                &Intersection::new(0.0, &cylinder),
+            );
+
+            assert_eq!(normal, Tuple::new_vector(nx, ny, nz))
+        }
+    }
+
+    #[test]
+    fn default_cylinder_is_infinite_and_open() {
+        let cylinder = Cylinder::<f64>::new(1);
+
+        assert_eq!(cylinder.minimum, f64::NEG_INFINITY);
+        assert_eq!(cylinder.maximum, f64::INFINITY);
+        assert!(!cylinder.closed)
+    }
+
+    #[test]
+    fn intersect_truncated_cylinder() {
+        let cylinder = Cylinder::<f64>::new_truncated(1, 1.0, 2.0, false);
+        let parameters = [
+            ((0.0, 1.5, 0.0), (0.1, 1.0, 0.0), 0),
+            ((0.0, 3.0, -5.0), (0.0, 0.0, 1.0), 0),
+            ((0.0, 0.0, -5.0), (0.0, 0.0, 1.0), 0),
+            ((0.0, 2.0, -5.0), (0.0, 0.0, 1.0), 0),
+            ((0.0, 1.0, -5.0), (0.0, 0.0, 1.0), 0),
+            ((0.0, 1.5, -2.0), (0.0, 0.0, 1.0), 2),
+        ];
+
+        for ((ox, oy, oz), (dx, dy, dz), count) in parameters {
+            let direction = Tuple::new_vector(dx, dy, dz).normalize();
+            let ray = Ray::new(Tuple::new_point(ox, oy, oz), direction);
+
+            assert_eq!(cylinder.local_intersect(ray).len(), count)
+        }
+    }
+
+    #[test]
+    fn intersect_caps_of_closed_cylinder() {
+        let cylinder = Cylinder::<f64>::new_truncated(1, 1.0, 2.0, true);
+        let parameters = [
+            ((0.0, 3.0, 0.0), (0.0, -1.0, 0.0), 2),
+            ((0.0, 3.0, -2.0), (0.0, -1.0, 2.0), 2),
+            ((0.0, 4.0, -2.0), (0.0, -1.0, 1.0), 2),
+            ((0.0, 0.0, -2.0), (0.0, 1.0, 2.0), 2),
+            ((0.0, -1.0, -2.0), (0.0, 1.0, 1.0), 2),
+        ];
+
+        for ((ox, oy, oz), (dx, dy, dz), count) in parameters {
+            let direction = Tuple::new_vector(dx, dy, dz).normalize();
+            let ray = Ray::new(Tuple::new_point(ox, oy, oz), direction);
+
+            assert_eq!(cylinder.local_intersect(ray).len(), count)
+        }
+    }
+
+    #[test]
+    fn normal_on_cylinder_caps() {
+        let cylinder = Cylinder::<f64>::new_truncated(1, 1.0, 2.0, true);
+        let parameters = [
+            ((0.0, 1.0, 0.0), (0.0, -1.0, 0.0)),
+            ((0.5, 1.0, 0.0), (0.0, -1.0, 0.0)),
+            ((0.0, 1.0, 0.5), (0.0, -1.0, 0.0)),
+            ((0.0, 2.0, 0.0), (0.0, 1.0, 0.0)),
+            ((0.5, 2.0, 0.0), (0.0, 1.0, 0.0)),
+            ((0.0, 2.0, 0.5), (0.0, 1.0, 0.0)),
+        ];
+
+        for ((px, py, pz), (nx, ny, nz)) in parameters {
+            let normal = cylinder.local_normal_at(
+                Tuple::new_point(px, py, pz),
+                &Intersection::new(0.0, &cylinder),
+            );
+
+            assert_eq!(normal, Tuple::new_vector(nx, ny, nz))
+        }
+    }
+
+    #[test]
+    fn bounds_of_truncated_cylinder() {
+        let bounds = Cylinder::<f64>::new_truncated(1, -5.0, 3.0, false).bounds();
+
+        assert_eq!(bounds.min, Tuple::new_point(-1.0, -5.0, -1.0));
+        assert_eq!(bounds.max, Tuple::new_point(1.0, 3.0, 1.0))
+    }
+
+    #[test]
+    fn intersect_large_scaled_cylinder() {
+        let mut cylinder = Cylinder::<f64>::new_truncated(1, -1.0, 1.0, true);
+        cylinder
+            .set_transformation(Matrix::scaling(1000.0, 1000.0, 1000.0))
+            .unwrap();
+        let parameters = [
+            ((-5000.0, 0.0, 0.0), (1.0, 0.0, 0.0)),
+            ((0.0, -5000.0, 0.0), (0.0, 1.0, 0.0)),
+        ];
+
+        for ((ox, oy, oz), (dx, dy, dz)) in parameters {
+            let ray = Ray::new(Tuple::new_point(ox, oy, oz), Tuple::new_vector(dx, dy, dz));
+            let inter = cylinder.intersect(ray);
+
+            assert_eq!(inter.len(), 2);
+            assert!((inter[0].value - 4000.0).abs() < 1e-6);
+            assert!((inter[1].value - 6000.0).abs() < 1e-6)
+        }
+    }
+}
@@ -0,0 +1,110 @@
+use crate::color::Color;
+use crate::light::{Light, LightSample};
+use crate::tuple::Tuple;
+use num::Float;
+
+/*
+    This file contains the directional light. It is a light that is
+    infinitely far away, like the sun, so it has no position and its
+    light reaches every point from the same direction
+*/
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DirectionalLight<T>
+where
+    T: Float,
+{
+    // the direction the light travels in, it does not have to be normalized
+    pub direction: Tuple<T>,
+    pub intensity: Color,
+}
+
+impl<T> DirectionalLight<T>
+where
+    T: Float,
+{
+    pub fn new(direction: Tuple<T>, intensity: Color) -> Self {
+        Self {
+            direction,
+            intensity,
+        }
+    }
+}
+
+impl<T> Light<T> for DirectionalLight<T>
+where
+    T: Float,
+    T: Send + Sync,
+{
+    fn intensity(&self) -> &Color {
+        &self.intensity
+    }
+
+    // the light is behind every object, so shadow rays
+    // are cast to infinity instead of to a position
+    fn samples(&self, _point: Tuple<T>) -> Vec<LightSample<T>> {
+        vec![LightSample {
+            direction: -self.direction.normalize(),
+            distance: T::infinity(),
+            intensity: self.intensity.clone(),
+        }]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::color::Color;
+    use crate::directional_light::DirectionalLight;
+    use crate::light::{lighting, Light};
+    use crate::material::Material;
+    use crate::sphere::Sphere;
+    use crate::tuple::Tuple;
+
+    #[test]
+    fn create_directional_light() {
+        let light = DirectionalLight::<f64>::new(
+            Tuple::new_vector(0.0, -1.0, 0.0),
+            Color::new(1.0, 1.0, 1.0),
+        );
+
+        assert_eq!(light.direction, Tuple::new_vector(0.0, -1.0, 0.0));
+        assert_eq!(light.intensity, Color::new(1.0, 1.0, 1.0))
+    }
+
+    #[test]
+    fn sample_comes_from_same_direction_everywhere() {
+        let light = DirectionalLight::<f64>::new(
+            Tuple::new_vector(0.0, -2.0, 0.0),
+            Color::new(1.0, 1.0, 1.0),
+        );
+
+        for (x, y, z) in [(0.0, 0.0, 0.0), (1e9, -3.0, 7.0), (-5.0, 1e6, 0.5)] {
+            let samples = light.samples(Tuple::new_point(x, y, z));
+
+            assert_eq!(samples.len(), 1);
+            assert_eq!(samples[0].direction, Tuple::new_vector(0.0, 1.0, 0.0));
+            assert_eq!(samples[0].distance, f64::INFINITY);
+            assert_eq!(samples[0].intensity, Color::new(1.0, 1.0, 1.0))
+        }
+    }
+
+    #[test]
+    fn lighting_with_directional_light() {
+        let material = Material::<f64>::default();
+        let eyev = Tuple::new_vector(0.0, 0.0, -1.0);
+        let normalv = Tuple::new_vector(0.0, 0.0, -1.0);
+        let light =
+            DirectionalLight::new(Tuple::new_vector(0.0, 0.0, 1.0), Color::new(1.0, 1.0, 1.0));
+
+        let result = lighting(
+            &material,
+            &Sphere::new(1),
+            &light,
+            Tuple::new_point(0.0, 0.0, 0.0),
+            eyev,
+            normalv,
+            1.0,
+        );
+        assert_eq!(result, Color::new(1.9, 1.9, 1.9))
+    }
+}
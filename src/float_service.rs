@@ -1,6 +1,8 @@
 use num::Float;
 
-const EPSILON: f64 = 0.00001;
+// tolerance used when comparing floats and to offset points
+// away from a surface
+pub const EPSILON: f64 = 0.00001;
 
 // compare floating numbers
 pub fn compare_floats<T>(x: T, y: T) -> bool
@@ -0,0 +1,420 @@
+use crate::bounds::Aabb;
+use crate::intersection::{Intersection, Intersections};
+use crate::ray::Ray;
+use crate::shape::{Shape, ShapeProperties};
+use crate::{Matrix, Tuple};
+use num::Float;
+use std::ops::AddAssign;
+
+/*
+    This file contains the group shape. A group has no surface of its own,
+    it contains other shapes that are transformed together with the group.
+    Groups can contain other groups to build models out of parts
+*/
+
+#[derive(Clone)]
+pub struct Group<T>
+where
+    T: Float,
+    T: AddAssign,
+{
+    properties: ShapeProperties<T>,
+    children: Vec<Box<dyn Shape<T>>>,
+    // the box around all the children, kept up to date
+    // because children can only be added through the group
+    bounds: Aabb<T>,
+}
+
+impl<T> Group<T>
+where
+    T: Float,
+    T: AddAssign,
+    T: Send + Sync + 'static,
+{
+    // create an empty group
+    pub fn new(id: i32) -> Self {
+        Self {
+            properties: ShapeProperties::new(id),
+            children: vec![],
+            bounds: Aabb::empty(),
+        }
+    }
+
+    // add a shape to the group, the shape is now
+    // transformed by the group and all of its parents
+    pub fn add_child(&mut self, mut child: Box<dyn Shape<T>>) {
+        child.set_parent_inverse(self.children_parent_inverse());
+        self.bounds = self.bounds.union(&child.parent_space_bounds());
+        self.children.push(child)
+    }
+
+    pub fn children(&self) -> &Vec<Box<dyn Shape<T>>> {
+        &self.children
+    }
+
+    // the combined inverse of the group and its parents
+    // is the parent inverse of every child
+    fn children_parent_inverse(&self) -> Matrix<T, 4> {
+        self.inverse().clone() * self.parent_inverse().clone()
+    }
+}
+
+impl<T> Shape<T> for Group<T>
+where
+    T: Float,
+    T: AddAssign,
+    T: Send + Sync + 'static,
+{
+    fn properties(&self) -> &ShapeProperties<T> {
+        &self.properties
+    }
+
+    fn properties_mut(&mut self) -> &mut ShapeProperties<T> {
+        &mut self.properties
+    }
+
+    // the ray is already in the space of the group, every child
+    // transforms it further into its own space. the children are
+    // skipped when the ray misses the box around them
+    fn local_intersect(&self, ray: Ray<T>) -> Intersections<'_, T> {
+        let mut intersections = Intersections::empty();
+        if !self.bounds.intersects(&ray) {
+            return intersections;
+        }
+        self.children
+            .iter()
+            .for_each(|child| intersections.merge(child.intersect(ray)));
+        intersections
+    }
+
+    // the children are asked one by one and the first hit ends the search
+    fn local_any_hit(&self, ray: Ray<T>, max_t: T) -> bool {
+        match self.bounds.intersection_range(&ray) {
+            Some((tmin, tmax)) if tmin < max_t && tmax > T::zero() => {
+                self.children.iter().any(|child| child.any_hit(ray, max_t))
+            }
+            _ => false,
+        }
+    }
+
+    // the intersections of a group always reference its children,
+    // so the normal of the group itself is never needed
+    fn local_normal_at(&self, _point: Tuple<T>, _hit: &Intersection<'_, T>) -> Tuple<T> {
+        unreachable!("a group is never hit itself, the normal is taken from the child that was hit")
+    }
+
+    fn bounds(&self) -> Aabb<T> {
+        self.bounds
+    }
+
+    fn update_children(&mut self) {
+        let parent_inverse = self.children_parent_inverse();
+        self.children
+            .iter_mut()
+            .for_each(|child| child.set_parent_inverse(parent_inverse.clone()))
+    }
+
+    fn includes(&self, other: &dyn Shape<T>) -> bool {
+        std::ptr::addr_eq(self, other) || self.children.iter().any(|child| child.includes(other))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cylinder::Cylinder;
+    use crate::group::Group;
+    use crate::intersection::Intersection;
+    use crate::ray::Ray;
+    use crate::shape::{same_shape, Shape};
+    use crate::sphere::Sphere;
+    use crate::{Matrix, Tuple};
+    use std::f64::consts::PI;
+
+    #[test]
+    fn create_group() {
+        let group = Group::<f64>::new(1);
+
+        assert_eq!(*group.transformation(), Matrix::identity_matrix());
+        assert!(group.children().is_empty())
+    }
+
+    #[test]
+    fn add_child_to_group() {
+        let mut group = Group::<f64>::new(1);
+        group
+            .set_transformation(Matrix::translation(1.0, 0.0, 0.0))
+            .unwrap();
+        group.add_child(Box::new(Sphere::new(2)));
+
+        assert_eq!(group.children().len(), 1);
+        assert_eq!(group.children()[0].id(), 2);
+        assert_eq!(group.children()[0].parent_inverse(), group.inverse())
+    }
+
+    #[test]
+    fn intersect_ray_with_empty_group() {
+        let group = Group::<f64>::new(1);
+        let ray = Ray::new(
+            Tuple::new_point(0.0, 0.0, 0.0),
+            Tuple::new_vector(0.0, 0.0, 1.0),
+        );
+
+        assert!(group.local_intersect(ray).is_empty())
+    }
+
+    #[test]
+    fn intersect_ray_with_group() {
+        let mut group = Group::<f64>::new(1);
+        let sphere_1 = Sphere::new(2);
+        let mut sphere_2 = Sphere::new(3);
+        sphere_2
+            .set_transformation(Matrix::translation(0.0, 0.0, -3.0))
+            .unwrap();
+        let mut sphere_3 = Sphere::new(4);
+        sphere_3
+            .set_transformation(Matrix::translation(5.0, 0.0, 0.0))
+            .unwrap();
+        group.add_child(Box::new(sphere_1));
+        group.add_child(Box::new(sphere_2));
+        group.add_child(Box::new(sphere_3));
+        let ray = Ray::new(
+            Tuple::new_point(0.0, 0.0, -5.0),
+            Tuple::new_vector(0.0, 0.0, 1.0),
+        );
+        let inter = group.local_intersect(ray);
+        let children = group.children();
+
+        assert_eq!(inter.len(), 4);
+        assert!(same_shape(inter[0].object, children[1].as_ref()));
+        assert!(same_shape(inter[1].object, children[1].as_ref()));
+        assert!(same_shape(inter[2].object, children[0].as_ref()));
+        assert!(same_shape(inter[3].object, children[0].as_ref()))
+    }
+
+    #[test]
+    fn intersect_transformed_group() {
+        let mut group = Group::<f64>::new(1);
+        group
+            .set_transformation(Matrix::scaling(2.0, 2.0, 2.0))
+            .unwrap();
+        let mut sphere = Sphere::new(2);
+        sphere
+            .set_transformation(Matrix::translation(5.0, 0.0, 0.0))
+            .unwrap();
+        group.add_child(Box::new(sphere));
+        let ray = Ray::new(
+            Tuple::new_point(10.0, 0.0, -10.0),
+            Tuple::new_vector(0.0, 0.0, 1.0),
+        );
+
+        assert_eq!(group.intersect(ray).len(), 2)
+    }
+
+    // a sphere in a group in a group, used to check the conversions
+    // between world and object space through every parent
+    fn nested_sphere() -> Group<f64> {
+        let mut outer = Group::new(1);
+        outer
+            .set_transformation(Matrix::rotate_y(PI / 2.0))
+            .unwrap();
+        let mut inner = Group::new(2);
+        inner
+            .set_transformation(Matrix::scaling(1.0, 2.0, 3.0))
+            .unwrap();
+        let mut sphere = Sphere::new(3);
+        sphere
+            .set_transformation(Matrix::translation(5.0, 0.0, 0.0))
+            .unwrap();
+        inner.add_child(Box::new(sphere));
+        outer.add_child(Box::new(inner));
+        outer
+    }
+
+    #[test]
+    fn convert_point_from_world_to_object_space() {
+        let outer = nested_sphere();
+        let ray = Ray::new(
+            Tuple::new_point(0.0, 0.0, -20.0),
+            Tuple::new_vector(0.0, 0.0, 1.0),
+        );
+        // the sphere is only reachable through the hit of a ray
+        let sphere = outer.intersect(ray).hit().unwrap().object;
+        let point = sphere.world_to_object(Tuple::new_point(0.0, 0.0, -6.0));
+
+        assert_eq!(sphere.id(), 3);
+        assert_eq!(point, Tuple::new_point(1.0, 0.0, 0.0))
+    }
+
+    #[test]
+    fn convert_normal_from_object_to_world_space() {
+        let outer = nested_sphere();
+        let ray = Ray::new(
+            Tuple::new_point(-2.0, 0.0, -20.0),
+            Tuple::new_vector(0.0, 0.0, 1.0),
+        );
+        let hit = outer.intersect(ray).hit().unwrap();
+        let value = 3.0_f64.sqrt() / 3.0;
+        let normal = hit
+            .object
+            .normal_to_world(Tuple::new_vector(value, value, value));
+
+        assert_eq!(normal, Tuple::new_vector(2.0 / 7.0, 3.0 / 7.0, -6.0 / 7.0))
+    }
+
+    #[test]
+    fn find_normal_on_child_object() {
+        let outer = nested_sphere();
+        let ray = Ray::new(
+            Tuple::new_point(1.7321, 1.1547, -20.0),
+            Tuple::new_vector(0.0, 0.0, 1.0),
+        );
+        let hit = outer.intersect(ray).hit().unwrap();
+        let normal = hit
+            .object
+            .normal_at(Tuple::new_point(1.7321, 1.1547, -5.5774), &hit);
+        let expected = Tuple::new_vector(0.2857, 0.4286, -0.8571);
+
+        for index in 0..3 {
+            assert!((normal[index] - expected[index]).abs() < 0.0001)
+        }
+    }
+
+    #[test]
+    fn transform_group_after_adding_children() {
+        let mut group = Group::<f64>::new(1);
+        group.add_child(Box::new(Sphere::new(2)));
+        group
+            .set_transformation(Matrix::translation(0.0, 0.0, 10.0))
+            .unwrap();
+        let child = &group.children()[0];
+        let hit = Intersection::new(1.0, child.as_ref());
+
+        assert_eq!(
+            child.normal_at(Tuple::new_point(0.0, 0.0, 9.0), &hit),
+            Tuple::new_vector(0.0, 0.0, -1.0)
+        )
+    }
+
+    #[test]
+    fn group_includes_nested_children() {
+        let outer = nested_sphere();
+        let sphere = Sphere::new(4);
+        let ray = Ray::new(
+            Tuple::new_point(0.0, 0.0, -20.0),
+            Tuple::new_vector(0.0, 0.0, 1.0),
+        );
+        let hit = outer.intersect(ray).hit().unwrap();
+
+        assert!(outer.includes(hit.object));
+        assert!(outer.includes(outer.children()[0].as_ref()));
+        assert!(!outer.includes(&sphere))
+    }
+
+    #[test]
+    fn place_cloned_group_twice() {
+        let mut model = Group::<f64>::new(1);
+        model.add_child(Box::new(Sphere::new(2)));
+        let mut left = model.clone();
+        left.set_transformation(Matrix::translation(-5.0, 0.0, 0.0))
+            .unwrap();
+        let mut right = model;
+        right
+            .set_transformation(Matrix::translation(5.0, 0.0, 0.0))
+            .unwrap();
+        let ray = Ray::new(
+            Tuple::new_point(-5.0, 0.0, -5.0),
+            Tuple::new_vector(0.0, 0.0, 1.0),
+        );
+
+        assert_eq!(left.intersect(ray).len(), 2);
+        assert!(right.intersect(ray).is_empty())
+    }
+
+    #[test]
+    fn group_bounds_contain_children() {
+        let mut group = Group::<f64>::new(1);
+        let mut sphere = Sphere::new(2);
+        sphere
+            .set_transformation(
+                Matrix::translation(2.0, 5.0, -3.0) * Matrix::scaling(2.0, 2.0, 2.0),
+            )
+            .unwrap();
+        let mut cylinder = Cylinder::new_truncated(3, -2.0, 2.0, false);
+        cylinder
+            .set_transformation(
+                Matrix::translation(-4.0, -1.0, 4.0) * Matrix::scaling(0.5, 1.0, 0.5),
+            )
+            .unwrap();
+        group.add_child(Box::new(sphere));
+        group.add_child(Box::new(cylinder));
+        let bounds = group.bounds();
+
+        assert_eq!(bounds.min, Tuple::new_point(-4.5, -3.0, -5.0));
+        assert_eq!(bounds.max, Tuple::new_point(4.0, 7.0, 4.5))
+    }
+
+    #[test]
+    fn ray_missing_group_bounds_skips_children() {
+        let mut group = Group::<f64>::new(1);
+        let mut sphere = Sphere::new(2);
+        sphere
+            .set_transformation(Matrix::translation(0.0, 5.0, 0.0))
+            .unwrap();
+        group.add_child(Box::new(sphere));
+        let miss = Ray::new(
+            Tuple::new_point(0.0, 0.0, -5.0),
+            Tuple::new_vector(0.0, 0.0, 1.0),
+        );
+        let hit = Ray::new(
+            Tuple::new_point(0.0, 5.0, -5.0),
+            Tuple::new_vector(0.0, 0.0, 1.0),
+        );
+
+        assert!(!group.bounds().intersects(&miss));
+        assert!(group.local_intersect(miss).is_empty());
+        assert_eq!(group.local_intersect(hit).len(), 2)
+    }
+
+    #[test]
+    fn nearly_parallel_ray_hits_grouped_sphere() {
+        let mut group = Group::<f64>::new(1);
+        group.add_child(Box::new(Sphere::new(2)));
+        let ray = Ray::new(
+            Tuple::new_point(1.00001, 0.0, -5.0),
+            Tuple::new_vector(-5e-6, 0.0, 1.0),
+        );
+
+        assert_eq!(
+            group.intersect(ray).len(),
+            Sphere::<f64>::new(3).intersect(ray).len()
+        );
+        assert_eq!(group.intersect(ray).len(), 2)
+    }
+
+    #[test]
+    fn any_hit_in_group_respects_distance() {
+        let mut group = Group::<f64>::new(1);
+        group
+            .set_transformation(Matrix::translation(0.0, 0.0, 5.0))
+            .unwrap();
+        group.add_child(Box::new(Sphere::new(2)));
+        let mut sphere = Sphere::new(3);
+        sphere
+            .set_transformation(Matrix::translation(5.0, 0.0, 0.0))
+            .unwrap();
+        group.add_child(Box::new(sphere));
+        let ray = Ray::new(
+            Tuple::new_point(0.0, 0.0, 0.0),
+            Tuple::new_vector(0.0, 0.0, 1.0),
+        );
+
+        assert!(group.any_hit(ray, 5.0));
+        assert!(!group.any_hit(ray, 4.0));
+        assert!(!group.any_hit(ray, 0.0));
+        let ray = Ray::new(
+            Tuple::new_point(0.0, 5.0, 0.0),
+            Tuple::new_vector(0.0, 0.0, 1.0),
+        );
+        assert!(!group.any_hit(ray, 100.0))
+    }
+}
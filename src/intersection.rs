@@ -1,127 +1,328 @@
+use crate::float_service::EPSILON;
 use crate::ray::Ray;
+use crate::shape::{same_shape, Shape};
+use crate::tuple::Tuple;
 use num::Float;
 use std::clone::Clone;
-use std::ops::{Index, IndexMut};
+use std::cmp::Ordering;
+use std::fmt::{Debug, Formatter};
+use std::ops::{AddAssign, Index};
+use std::slice::Iter;
 
-// this trait is used to identify shape structs
-pub trait IntersectionObject<T>
+// an intersection references the shape that was hit
+// instead of owning a copy of it. u and v are the barycentric
+// coordinates of the hit on a triangle and zero for other shapes
+#[derive(Clone, Copy)]
+pub struct Intersection<'a, T>
 where
     T: Float,
+    T: AddAssign,
 {
-    type Object;
+    pub value: T,
+    pub object: &'a dyn Shape<T>,
+    pub u: T,
+    pub v: T,
+}
+
+impl<'a, T> Intersection<'a, T>
+where
+    T: Float,
+    T: AddAssign,
+{
+    pub fn new(value: T, object: &'a dyn Shape<T>) -> Self {
+        Self::new_with_uv(value, object, T::zero(), T::zero())
+    }
 
-    // return an array of 2 intersections where the ray has intersected
-    // the given object.
-    fn intersect(&self, ray: Ray<T>) -> Result<Intersections<T, Self::Object, 2>, &'static str>
-    where
-        <Self as IntersectionObject<T>>::Object: IntersectionObject<T> + Clone;
+    // create an intersection that remembers where on
+    // the surface of a triangle the hit happened
+    pub fn new_with_uv(value: T, object: &'a dyn Shape<T>, u: T, v: T) -> Self {
+        Self {
+            value,
+            object,
+            u,
+            v,
+        }
+    }
 }
 
-#[derive(Debug, PartialEq, Clone)]
-pub struct Intersection<T, O>
+// shapes are not Debug, so only the id of the shape is shown
+impl<T> Debug for Intersection<'_, T>
 where
     T: Float,
-    O: IntersectionObject<T> + Clone,
+    T: AddAssign,
+    T: Debug,
 {
-    pub value: T,
-    pub object: O,
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Intersection")
+            .field("value", &self.value)
+            .field("object", &self.object.id())
+            .field("u", &self.u)
+            .field("v", &self.v)
+            .finish()
+    }
 }
 
-impl<T, O> Intersection<T, O>
+// intersections are equal when they have the same
+// value and reference the same shape
+impl<T> PartialEq for Intersection<'_, T>
 where
     T: Float,
-    O: IntersectionObject<T> + Clone,
+    T: AddAssign,
 {
-    pub fn new(value: T, object: O) -> Self {
-        Self { value, object }
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value && same_shape(self.object, other.object)
     }
 }
 
-// array primitive for holding all the intersection
-pub struct Intersections<T, O, const N: usize>
+// growable collection of intersections that is always sorted
+// by value, lowest value first
+#[derive(Debug, Clone)]
+pub struct Intersections<'a, T>
 where
     T: Float,
-    O: IntersectionObject<T> + Clone,
+    T: AddAssign,
 {
-    data: [Intersection<T, O>; N],
+    data: Vec<Intersection<'a, T>>,
 }
 
-impl<T, O, const N: usize> Intersections<T, O, N>
+impl<'a, T> Intersections<'a, T>
 where
     T: Float,
-    O: IntersectionObject<T> + Clone,
+    T: AddAssign,
 {
-    pub fn new(data: [Intersection<T, O>; N]) -> Self {
+    // create a new collection, the given intersections are sorted
+    pub fn new(mut data: Vec<Intersection<'a, T>>) -> Self {
+        data.sort_by(|a, b| a.value.partial_cmp(&b.value).unwrap_or(Ordering::Equal));
         Self { data }
     }
 
-    // return the intersection with closest to 0.0
-    // value
-    pub fn hit(&self) -> Option<Intersection<T, O>> {
-        let mut closest_intersection = None;
-
-        self.data.iter().cloned().for_each(|inter| {
-            if inter.value > T::zero() {
-                match closest_intersection.is_none() {
-                    true => {
-                        closest_intersection = Some(inter);
-                    }
-                    false => {
-                        if closest_intersection.as_ref().unwrap().value > inter.value {
-                            closest_intersection = Some(inter)
-                        }
-                    }
-                }
-            }
-        });
-        closest_intersection
+    // create a collection without intersections, used for misses
+    pub fn empty() -> Self {
+        Self { data: vec![] }
+    }
+
+    // insert the intersection at its sorted position, intersections
+    // with an equal value keep the order they were added in
+    pub fn push(&mut self, intersection: Intersection<'a, T>) {
+        let index = self
+            .data
+            .partition_point(|inter| inter.value <= intersection.value);
+        self.data.insert(index, intersection)
+    }
+
+    // merge another sorted collection into this one
+    pub fn merge(&mut self, other: Intersections<'a, T>) {
+        self.data.extend(other.data);
+        self.data
+            .sort_by(|a, b| a.value.partial_cmp(&b.value).unwrap_or(Ordering::Equal));
+    }
+
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    pub fn iter(&self) -> Iter<'_, Intersection<'a, T>> {
+        self.data.iter()
+    }
+
+    // return the intersection with the lowest non-negative value,
+    // because the data is sorted this is the first positive one
+    pub fn hit(&self) -> Option<Intersection<'a, T>> {
+        self.data
+            .iter()
+            .find(|inter| inter.value > T::zero())
+            .cloned()
     }
 }
 
-impl<T, O, const N: usize> Index<usize> for Intersections<T, O, N>
+impl<'a, T> Index<usize> for Intersections<'a, T>
 where
     T: Float,
-    O: IntersectionObject<T> + Clone,
+    T: AddAssign,
 {
-    type Output = Intersection<T, O>;
+    type Output = Intersection<'a, T>;
 
     fn index(&self, index: usize) -> &Self::Output {
         &self.data[index]
     }
 }
 
-impl<T, O, const N: usize> IndexMut<usize> for Intersections<T, O, N>
+impl<'a, T> IntoIterator for Intersections<'a, T>
 where
     T: Float,
-    O: IntersectionObject<T> + Clone,
+    T: AddAssign,
 {
-    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
-        &mut self.data[index]
+    type Item = Intersection<'a, T>;
+    type IntoIter = std::vec::IntoIter<Self::Item>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.data.into_iter()
     }
 }
 
+// the state of a hit that every shading step needs, computed once
+pub struct Computations<'a, T>
+where
+    T: Float,
+    T: AddAssign,
+{
+    pub value: T,
+    pub object: &'a dyn Shape<T>,
+    pub point: Tuple<T>,
+    pub eyev: Tuple<T>,
+    pub normalv: Tuple<T>,
+    pub inside: bool,
+    // the direction of the ray after it bounces off the surface
+    pub reflectv: Tuple<T>,
+    // the point moved slightly above and below the surface so rays
+    // that start there do not intersect the surface they start on
+    pub over_point: Tuple<T>,
+    pub under_point: Tuple<T>,
+    // the refractive indices of the material the ray comes
+    // from and the material the ray goes into
+    pub n1: T,
+    pub n2: T,
+}
+
+// precompute the state of the hit. the normal is flipped when the ray
+// starts inside the object so it always points towards the eye. all the
+// intersections of the ray are passed for the media the ray travels through
+pub fn prepare_computations<'a, T>(
+    hit: &Intersection<'a, T>,
+    ray: Ray<T>,
+    intersections: &Intersections<'a, T>,
+) -> Computations<'a, T>
+where
+    T: Float,
+    T: AddAssign,
+{
+    let point = ray.position(hit.value);
+    let eyev = -ray.direction;
+    let mut normalv = hit.object.normal_at(point, hit);
+    let inside = normalv.dot_product(&eyev) < T::zero();
+    if inside {
+        normalv = -normalv
+    }
+    let offset = normalv * T::from(EPSILON).unwrap();
+    let (n1, n2) = refractive_indices(hit, intersections);
+
+    Computations {
+        value: hit.value,
+        object: hit.object,
+        point,
+        eyev,
+        normalv,
+        inside,
+        reflectv: ray.direction.reflect(&normalv),
+        over_point: point + offset,
+        under_point: point - offset,
+        n1,
+        n2,
+    }
+}
+
+// find the refractive indices on both sides of the hit. the intersections
+// are walked in order while keeping a list of the objects the ray is inside
+// of, an object is entered at its first intersection and left at the next
+fn refractive_indices<'a, T>(
+    hit: &Intersection<'a, T>,
+    intersections: &Intersections<'a, T>,
+) -> (T, T)
+where
+    T: Float,
+    T: AddAssign,
+{
+    let refractive_index = |containers: &Vec<&dyn Shape<T>>| match containers.last() {
+        None => T::one(),
+        Some(object) => object.material().refractive_index,
+    };
+    let mut containers: Vec<&dyn Shape<T>> = vec![];
+    let mut n1 = T::one();
+
+    for intersection in intersections.iter() {
+        if intersection == hit {
+            n1 = refractive_index(&containers);
+        }
+        match containers
+            .iter()
+            .position(|object| same_shape(*object, intersection.object))
+        {
+            Some(index) => {
+                containers.remove(index);
+            }
+            None => containers.push(intersection.object),
+        }
+        if intersection == hit {
+            return (n1, refractive_index(&containers));
+        }
+    }
+    (n1, refractive_index(&containers))
+}
+
+// the schlick approximation of the fresnel effect, it returns the part of
+// the light that is reflected. the rest of the light is refracted
+pub fn schlick<T>(comps: &Computations<'_, T>) -> T
+where
+    T: Float,
+    T: AddAssign,
+{
+    let mut cos = comps.eyev.dot_product(&comps.normalv);
+    // total internal reflection can only happen when n1 > n2
+    if comps.n1 > comps.n2 {
+        let ratio = comps.n1 / comps.n2;
+        let sin2_t = ratio.powi(2) * (T::one() - cos.powi(2));
+        if sin2_t > T::one() {
+            return T::one();
+        }
+        // use the cosine of the refracted angle instead
+        cos = (T::one() - sin2_t).sqrt();
+    }
+    let r0 = ((comps.n1 - comps.n2) / (comps.n1 + comps.n2)).powi(2);
+    r0 + (T::one() - r0) * (T::one() - cos).powi(5)
+}
+
+#[cfg(test)]
 mod tests {
-    use crate::intersection::{Intersection, IntersectionObject, Intersections};
+    use crate::float_service::EPSILON;
+    use crate::intersection::{prepare_computations, schlick, Intersection, Intersections};
+    use crate::material::Material;
+    use crate::plane::Plane;
     use crate::ray::Ray;
+    use crate::shape::{same_shape, Shape};
     use crate::sphere::Sphere;
-    use crate::Tuple;
+    use crate::{Matrix, Tuple};
 
     #[test]
     fn create_intersection() {
         let sphere = Sphere::<f64>::new(1);
-        let intersection = Intersection::<f64, Sphere<f64>>::new(1.0, sphere.clone());
+        let intersection = Intersection::new(1.0, &sphere);
 
         assert_eq!(intersection.value, 1.0);
-        assert_eq!(intersection.object, sphere)
+        assert!(same_shape(intersection.object, &sphere));
+        assert_eq!(intersection.u, 0.0);
+        assert_eq!(intersection.v, 0.0)
+    }
+
+    #[test]
+    fn create_intersection_with_uv() {
+        let sphere = Sphere::<f64>::new(1);
+        let intersection = Intersection::new_with_uv(3.5, &sphere, 0.2, 0.4);
+
+        assert_eq!(intersection.value, 3.5);
+        assert_eq!(intersection.u, 0.2);
+        assert_eq!(intersection.v, 0.4)
     }
 
     #[test]
     fn create_intersections_check_values() {
         let sphere = Sphere::<f64>::new(1);
-        let intersection_1 = Intersection::<f64, Sphere<f64>>::new(1.0, sphere.clone());
-        let intersection_2 = Intersection::<f64, Sphere<f64>>::new(2.0, sphere.clone());
-        let intersections =
-            Intersections::<f64, Sphere<f64>, 2>::new([intersection_1, intersection_2]);
+        let intersection_1 = Intersection::new(1.0, &sphere);
+        let intersection_2 = Intersection::new(2.0, &sphere);
+        let intersections = Intersections::new(vec![intersection_1, intersection_2]);
 
         assert_eq!(intersections[0].value, 1.0);
         assert_eq!(intersections[1].value, 2.0)
@@ -134,25 +335,19 @@ mod tests {
             Tuple::<f64>::new_point(0.0, 0.0, -5.0),
             Tuple::<f64>::new_vector(0.0, 0.0, 1.0),
         );
-        match sphere.intersect(ray) {
-            Ok(intersection) => {
-                assert_eq!(intersection[0].object, sphere);
-                assert_eq!(intersection[1].object, sphere)
-            }
-            Err(_) => {
-                assert_eq!(true, false)
-            }
-        }
+        let intersection = sphere.intersect(ray);
+
+        assert!(same_shape(intersection[0].object, &sphere));
+        assert!(same_shape(intersection[1].object, &sphere))
     }
 
     #[test]
     fn return_hit() {
         let sphere = Sphere::<f64>::new(1);
-        let intersection_1 = Intersection::<f64, Sphere<f64>>::new(1.0, sphere.clone());
-        let intersection_2 = Intersection::<f64, Sphere<f64>>::new(2.0, sphere.clone());
+        let intersection_1 = Intersection::new(1.0, &sphere);
+        let intersection_2 = Intersection::new(2.0, &sphere);
 
-        let intersections =
-            Intersections::<f64, Sphere<f64>, 2>::new([intersection_1.clone(), intersection_2]);
+        let intersections = Intersections::new(vec![intersection_1, intersection_2]);
 
         match intersections.hit() {
             None => {
@@ -167,10 +362,9 @@ mod tests {
     #[test]
     fn return_hit_some_negative() {
         let sphere = Sphere::<f64>::new(1);
-        let intersection_1 = Intersection::<f64, Sphere<f64>>::new(-1.0, sphere.clone());
-        let intersection_2 = Intersection::<f64, Sphere<f64>>::new(1.0, sphere.clone());
-        let intersections =
-            Intersections::<f64, Sphere<f64>, 2>::new([intersection_1, intersection_2.clone()]);
+        let intersection_1 = Intersection::new(-1.0, &sphere);
+        let intersection_2 = Intersection::new(1.0, &sphere);
+        let intersections = Intersections::new(vec![intersection_1, intersection_2]);
 
         match intersections.hit() {
             None => {
@@ -185,10 +379,9 @@ mod tests {
     #[test]
     fn return_no_hit() {
         let sphere = Sphere::<f64>::new(1);
-        let intersection_1 = Intersection::<f64, Sphere<f64>>::new(-2.0, sphere.clone());
-        let intersection_2 = Intersection::<f64, Sphere<f64>>::new(-1.0, sphere);
-        let intersections =
-            Intersections::<f64, Sphere<f64>, 2>::new([intersection_1, intersection_2]);
+        let intersection_1 = Intersection::new(-2.0, &sphere);
+        let intersection_2 = Intersection::new(-1.0, &sphere);
+        let intersections = Intersections::new(vec![intersection_1, intersection_2]);
 
         match intersections.hit() {
             None => {
@@ -203,15 +396,15 @@ mod tests {
     #[test]
     fn return_lowest_multiple_intersections() {
         let sphere = Sphere::<f64>::new(1);
-        let intersection_1 = Intersection::<f64, Sphere<f64>>::new(5.0, sphere.clone());
-        let intersection_2 = Intersection::<f64, Sphere<f64>>::new(7.0, sphere.clone());
-        let intersection_3 = Intersection::<f64, Sphere<f64>>::new(-3.0, sphere.clone());
-        let intersection_4 = Intersection::<f64, Sphere<f64>>::new(2.0, sphere.clone());
-        let intersections = Intersections::<f64, Sphere<f64>, 4>::new([
+        let intersection_1 = Intersection::new(5.0, &sphere);
+        let intersection_2 = Intersection::new(7.0, &sphere);
+        let intersection_3 = Intersection::new(-3.0, &sphere);
+        let intersection_4 = Intersection::new(2.0, &sphere);
+        let intersections = Intersections::new(vec![
             intersection_1,
             intersection_2,
             intersection_3,
-            intersection_4.clone(),
+            intersection_4,
         ]);
 
         match intersections.hit() {
@@ -223,4 +416,245 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn intersections_are_sorted() {
+        let sphere = Sphere::<f64>::new(1);
+        let intersections = Intersections::new(vec![
+            Intersection::new(5.0, &sphere),
+            Intersection::new(-3.0, &sphere),
+            Intersection::new(2.0, &sphere),
+        ]);
+
+        assert_eq!(intersections.len(), 3);
+        assert_eq!(intersections[0].value, -3.0);
+        assert_eq!(intersections[1].value, 2.0);
+        assert_eq!(intersections[2].value, 5.0)
+    }
+
+    #[test]
+    fn push_keeps_intersections_sorted() {
+        let sphere = Sphere::<f64>::new(1);
+        let mut intersections = Intersections::empty();
+        intersections.push(Intersection::new(3.0, &sphere));
+        intersections.push(Intersection::new(1.0, &sphere));
+        intersections.push(Intersection::new(2.0, &sphere));
+
+        let values: Vec<f64> = intersections.iter().map(|inter| inter.value).collect();
+        assert_eq!(values, vec![1.0, 2.0, 3.0])
+    }
+
+    #[test]
+    fn merge_intersections() {
+        let sphere = Sphere::<f64>::new(1);
+        let mut intersections_1 = Intersections::new(vec![
+            Intersection::new(1.0, &sphere),
+            Intersection::new(4.0, &sphere),
+        ]);
+        let intersections_2 = Intersections::new(vec![
+            Intersection::new(2.0, &sphere),
+            Intersection::new(6.0, &sphere),
+        ]);
+        intersections_1.merge(intersections_2);
+
+        let values: Vec<f64> = intersections_1.iter().map(|inter| inter.value).collect();
+        assert_eq!(values, vec![1.0, 2.0, 4.0, 6.0])
+    }
+
+    #[test]
+    fn empty_intersections_have_no_hit() {
+        let intersections = Intersections::<f64>::empty();
+
+        assert!(intersections.is_empty());
+        assert!(intersections.hit().is_none())
+    }
+
+    #[test]
+    fn precompute_hit_state() {
+        let ray = Ray::new(
+            Tuple::new_point(0.0, 0.0, -5.0),
+            Tuple::new_vector(0.0, 0.0, 1.0),
+        );
+        let sphere = Sphere::<f64>::new(1);
+        let hit = Intersection::new(4.0, &sphere);
+        let comps = prepare_computations(&hit, ray, &Intersections::new(vec![hit]));
+
+        assert_eq!(comps.value, hit.value);
+        assert!(same_shape(comps.object, &sphere));
+        assert_eq!(comps.point, Tuple::new_point(0.0, 0.0, -1.0));
+        assert_eq!(comps.eyev, Tuple::new_vector(0.0, 0.0, -1.0));
+        assert_eq!(comps.normalv, Tuple::new_vector(0.0, 0.0, -1.0))
+    }
+
+    #[test]
+    fn precompute_hit_outside() {
+        let ray = Ray::new(
+            Tuple::new_point(0.0, 0.0, -5.0),
+            Tuple::new_vector(0.0, 0.0, 1.0),
+        );
+        let sphere = Sphere::<f64>::new(1);
+        let hit = Intersection::new(4.0, &sphere);
+        let comps = prepare_computations(&hit, ray, &Intersections::new(vec![hit]));
+
+        assert!(!comps.inside)
+    }
+
+    #[test]
+    fn precompute_hit_inside() {
+        let ray = Ray::new(
+            Tuple::new_point(0.0, 0.0, 0.0),
+            Tuple::new_vector(0.0, 0.0, 1.0),
+        );
+        let sphere = Sphere::<f64>::new(1);
+        let hit = Intersection::new(1.0, &sphere);
+        let comps = prepare_computations(&hit, ray, &Intersections::new(vec![hit]));
+
+        assert_eq!(comps.point, Tuple::new_point(0.0, 0.0, 1.0));
+        assert_eq!(comps.eyev, Tuple::new_vector(0.0, 0.0, -1.0));
+        assert!(comps.inside);
+        // the normal is inverted because it points away from the eye
+        assert_eq!(comps.normalv, Tuple::new_vector(0.0, 0.0, -1.0))
+    }
+
+    #[test]
+    fn hit_offsets_over_point() {
+        let ray = Ray::new(
+            Tuple::new_point(0.0, 0.0, -5.0),
+            Tuple::new_vector(0.0, 0.0, 1.0),
+        );
+        let mut sphere = Sphere::<f64>::new(1);
+        sphere
+            .set_transformation(Matrix::translation(0.0, 0.0, 1.0))
+            .unwrap();
+        let hit = Intersection::new(5.0, &sphere);
+        let comps = prepare_computations(&hit, ray, &Intersections::new(vec![hit]));
+
+        assert!(comps.over_point.z < -EPSILON / 2.0);
+        assert!(comps.point.z > comps.over_point.z)
+    }
+
+    #[test]
+    fn hit_offsets_under_point() {
+        let ray = Ray::new(
+            Tuple::new_point(0.0, 0.0, -5.0),
+            Tuple::new_vector(0.0, 0.0, 1.0),
+        );
+        let mut sphere = Sphere::<f64>::new(1);
+        sphere
+            .set_transformation(Matrix::translation(0.0, 0.0, 1.0))
+            .unwrap();
+        let hit = Intersection::new(5.0, &sphere);
+        let comps = prepare_computations(&hit, ray, &Intersections::new(vec![hit]));
+
+        assert!(comps.under_point.z > EPSILON / 2.0);
+        assert!(comps.point.z < comps.under_point.z)
+    }
+
+    #[test]
+    fn precompute_reflection_vector() {
+        let value = std::f64::consts::FRAC_1_SQRT_2;
+        let ray = Ray::new(
+            Tuple::new_point(0.0, 1.0, -1.0),
+            Tuple::new_vector(0.0, -value, value),
+        );
+        let plane = Plane::<f64>::new(1);
+        let hit = Intersection::new(std::f64::consts::SQRT_2, &plane);
+        let comps = prepare_computations(&hit, ray, &Intersections::new(vec![hit]));
+
+        assert_eq!(comps.reflectv, Tuple::new_vector(0.0, value, value))
+    }
+
+    #[test]
+    fn find_n1_and_n2_at_intersections() {
+        let mut a = Sphere::glass_sphere(1);
+        a.set_transformation(Matrix::scaling(2.0, 2.0, 2.0))
+            .unwrap();
+        let mut b = Sphere::glass_sphere(2);
+        b.set_transformation(Matrix::translation(0.0, 0.0, -0.25))
+            .unwrap();
+        b.set_material(Material {
+            refractive_index: 2.0,
+            ..b.material().clone()
+        });
+        let mut c = Sphere::glass_sphere(3);
+        c.set_transformation(Matrix::translation(0.0, 0.0, 0.25))
+            .unwrap();
+        c.set_material(Material {
+            refractive_index: 2.5,
+            ..c.material().clone()
+        });
+        let ray = Ray::new(
+            Tuple::new_point(0.0, 0.0, -4.0),
+            Tuple::new_vector(0.0, 0.0, 1.0),
+        );
+        let intersections = Intersections::new(vec![
+            Intersection::new(2.0, &a),
+            Intersection::new(2.75, &b),
+            Intersection::new(3.25, &c),
+            Intersection::new(4.75, &b),
+            Intersection::new(5.25, &c),
+            Intersection::new(6.0, &a),
+        ]);
+        let parameters = [
+            (1.0, 1.5),
+            (1.5, 2.0),
+            (2.0, 2.5),
+            (2.5, 2.5),
+            (2.5, 1.5),
+            (1.5, 1.0),
+        ];
+
+        for (index, (n1, n2)) in parameters.into_iter().enumerate() {
+            let comps = prepare_computations(&intersections[index], ray, &intersections);
+
+            assert_eq!(comps.n1, n1);
+            assert_eq!(comps.n2, n2)
+        }
+    }
+
+    #[test]
+    fn schlick_under_total_internal_reflection() {
+        let value = std::f64::consts::FRAC_1_SQRT_2;
+        let sphere = Sphere::glass_sphere(1);
+        let ray = Ray::new(
+            Tuple::new_point(0.0, 0.0, value),
+            Tuple::new_vector(0.0, 1.0, 0.0),
+        );
+        let intersections = Intersections::new(vec![
+            Intersection::new(-value, &sphere),
+            Intersection::new(value, &sphere),
+        ]);
+        let comps = prepare_computations(&intersections[1], ray, &intersections);
+
+        assert_eq!(schlick(&comps), 1.0)
+    }
+
+    #[test]
+    fn schlick_with_perpendicular_ray() {
+        let sphere = Sphere::glass_sphere(1);
+        let ray = Ray::new(
+            Tuple::new_point(0.0, 0.0, 0.0),
+            Tuple::new_vector(0.0, 1.0, 0.0),
+        );
+        let intersections = Intersections::new(vec![
+            Intersection::new(-1.0, &sphere),
+            Intersection::new(1.0, &sphere),
+        ]);
+        let comps = prepare_computations(&intersections[1], ray, &intersections);
+
+        assert!((schlick(&comps) - 0.04).abs() < EPSILON)
+    }
+
+    #[test]
+    fn schlick_with_small_angle_and_larger_n2() {
+        let sphere = Sphere::glass_sphere(1);
+        let ray = Ray::new(
+            Tuple::new_point(0.0, 0.99, -2.0),
+            Tuple::new_vector(0.0, 0.0, 1.0),
+        );
+        let intersections = Intersections::new(vec![Intersection::new(1.8589, &sphere)]);
+        let comps = prepare_computations(&intersections[0], ray, &intersections);
+
+        assert!((schlick(&comps) - 0.48873).abs() < 0.0001)
+    }
 }
@@ -0,0 +1,39 @@
+#![warn(rust_2018_idioms)]
+
+pub use crate::matrix::Matrix;
+pub use crate::tuple::Tuple;
+
+pub mod area_light;
+pub mod bounds;
+pub mod bvh;
+pub mod camera;
+pub mod canvas;
+pub mod color;
+pub mod cone;
+pub mod csg;
+pub mod cube;
+pub mod cylinder;
+pub mod directional_light;
+pub mod float_service;
+pub mod group;
+pub mod intersection;
+pub mod light;
+pub mod material;
+pub mod matrix;
+pub mod obj_parser;
+pub mod pattern;
+pub mod perlin;
+pub mod plane;
+pub mod projectile;
+pub mod ray;
+pub mod shape;
+pub mod sphere;
+pub mod spot_light;
+pub mod triangle;
+pub mod tuple;
+pub mod world;
+
+/*
+    This file contains the modules of the ray tracer. They are kept in
+    a library so main.rs only has to put a scene together and render it
+*/
@@ -0,0 +1,446 @@
+use crate::color::Color;
+use crate::material::Material;
+use crate::shape::Shape;
+use crate::tuple::Tuple;
+use num::Float;
+use std::ops::AddAssign;
+
+/*
+    This file contains the light trait, the point light and the lighting
+    function that shades a point on a surface with the Phong reflection model
+*/
+
+// the light that reaches a point from one sample of a light source.
+// the direction points from the point towards the light and the
+// distance is how far a shadow ray has to travel to reach the light
+#[derive(Debug, Clone, PartialEq)]
+pub struct LightSample<T>
+where
+    T: Float,
+{
+    pub direction: Tuple<T>,
+    pub distance: T,
+    pub intensity: Color,
+}
+
+// a light source is seen from a point as one or more samples, every
+// sample is checked for shadows and shaded on its own
+pub trait Light<T>: Send + Sync
+where
+    T: Float,
+{
+    fn intensity(&self) -> &Color;
+
+    // return the intensity that is used for the ambient light at the
+    // point, most lights add the same ambient light everywhere
+    fn ambient_intensity(&self, _point: Tuple<T>) -> Color {
+        self.intensity().clone()
+    }
+
+    // return the samples of the light as seen from the point, the
+    // same point has to give the same samples every time
+    fn samples(&self, point: Tuple<T>) -> Vec<LightSample<T>>;
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PointLight<T>
+where
+    T: Float,
+{
+    pub position: Tuple<T>,
+    pub intensity: Color,
+}
+
+impl<T> PointLight<T>
+where
+    T: Float,
+{
+    // create a new point light that has no size and
+    // emits light with the given intensity
+    pub fn new(position: Tuple<T>, intensity: Color) -> Self {
+        Self {
+            position,
+            intensity,
+        }
+    }
+}
+
+impl<T> Light<T> for PointLight<T>
+where
+    T: Float,
+    T: Send + Sync,
+{
+    fn intensity(&self) -> &Color {
+        &self.intensity
+    }
+
+    fn samples(&self, point: Tuple<T>) -> Vec<LightSample<T>> {
+        vec![sample_towards(self.position, point, self.intensity.clone())]
+    }
+}
+
+// create the sample of a light at the given position
+pub fn sample_towards<T>(position: Tuple<T>, point: Tuple<T>, intensity: Color) -> LightSample<T>
+where
+    T: Float,
+{
+    let vector = position - point;
+    LightSample {
+        direction: vector.normalize(),
+        distance: vector.magnitude(),
+        intensity,
+    }
+}
+
+// calculate the color of a point on a surface by adding the
+// ambient, diffuse and specular contributions of the light. the
+// diffuse and specular contributions are averaged over the samples
+// of the light and scaled by the visible part of the light, a point
+// that is completely in shadow only receives the ambient contribution.
+// the object is needed to find the color of a pattern at the point
+pub fn lighting<T>(
+    material: &Material<T>,
+    object: &dyn Shape<T>,
+    light: &dyn Light<T>,
+    point: Tuple<T>,
+    eyev: Tuple<T>,
+    normalv: Tuple<T>,
+    visible: T,
+) -> Color
+where
+    T: Float,
+    T: AddAssign,
+{
+    let black = Color::new(0.0, 0.0, 0.0);
+    let color = match &material.pattern {
+        None => material.color.clone(),
+        Some(pattern) => pattern.color_at_object(object, point),
+    };
+    let ambient =
+        color.clone() * light.ambient_intensity(point) * material.ambient.to_f64().unwrap();
+    if visible <= T::zero() {
+        return ambient;
+    }
+
+    let samples = light.samples(point);
+    let sum = samples.iter().fold(black.clone(), |sum, sample| {
+        let lightv = sample.direction;
+
+        // a negative cosine between the light and normal vector means
+        // the light is on the other side of the surface
+        let light_dot_normal = lightv.dot_product(&normalv);
+        if light_dot_normal < T::zero() {
+            return sum;
+        }
+
+        let effective_color = color.clone() * sample.intensity.clone();
+        let diffuse = effective_color * (material.diffuse * light_dot_normal).to_f64().unwrap();
+
+        // reflect the inverted light vector around the normal, a negative
+        // cosine with the eye vector means the light reflects away from the eye
+        let reflectv = (-lightv).reflect(&normalv);
+        let reflect_dot_eye = reflectv.dot_product(&eyev);
+        let specular = match reflect_dot_eye <= T::zero() {
+            true => black.clone(),
+            false => {
+                let factor = reflect_dot_eye.powf(material.shininess);
+                sample.intensity.clone() * (material.specular * factor).to_f64().unwrap()
+            }
+        };
+        sum + diffuse + specular
+    });
+
+    let scale = visible.to_f64().unwrap() / samples.len() as f64;
+    ambient + sum * scale
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::area_light::AreaLight;
+    use crate::color::Color;
+    use crate::light::{lighting, Light, PointLight};
+    use crate::material::Material;
+    use crate::pattern::StripePattern;
+    use crate::sphere::Sphere;
+    use crate::tuple::Tuple;
+    use std::f64::consts::FRAC_1_SQRT_2;
+
+    #[test]
+    fn create_point_light() {
+        let position = Tuple::<f64>::new_point(0.0, 0.0, 0.0);
+        let intensity = Color::new(1.0, 1.0, 1.0);
+        let light = PointLight::new(position, intensity.clone());
+
+        assert_eq!(light.position, position);
+        assert_eq!(light.intensity, intensity)
+    }
+
+    #[test]
+    fn lighting_eye_between_light_and_surface() {
+        let material = Material::<f64>::default();
+        let position = Tuple::<f64>::new_point(0.0, 0.0, 0.0);
+        let eyev = Tuple::<f64>::new_vector(0.0, 0.0, -1.0);
+        let normalv = Tuple::<f64>::new_vector(0.0, 0.0, -1.0);
+        let light = PointLight::new(
+            Tuple::<f64>::new_point(0.0, 0.0, -10.0),
+            Color::new(1.0, 1.0, 1.0),
+        );
+
+        let result = lighting(
+            &material,
+            &Sphere::new(1),
+            &light,
+            position,
+            eyev,
+            normalv,
+            1.0,
+        );
+        assert_eq!(result, Color::new(1.9, 1.9, 1.9))
+    }
+
+    #[test]
+    fn lighting_eye_offset_45_degrees() {
+        let material = Material::<f64>::default();
+        let position = Tuple::<f64>::new_point(0.0, 0.0, 0.0);
+        let eyev = Tuple::<f64>::new_vector(0.0, FRAC_1_SQRT_2, -FRAC_1_SQRT_2);
+        let normalv = Tuple::<f64>::new_vector(0.0, 0.0, -1.0);
+        let light = PointLight::new(
+            Tuple::<f64>::new_point(0.0, 0.0, -10.0),
+            Color::new(1.0, 1.0, 1.0),
+        );
+
+        let result = lighting(
+            &material,
+            &Sphere::new(1),
+            &light,
+            position,
+            eyev,
+            normalv,
+            1.0,
+        );
+        assert_eq!(result, Color::new(1.0, 1.0, 1.0))
+    }
+
+    #[test]
+    fn lighting_light_offset_45_degrees() {
+        let material = Material::<f64>::default();
+        let position = Tuple::<f64>::new_point(0.0, 0.0, 0.0);
+        let eyev = Tuple::<f64>::new_vector(0.0, 0.0, -1.0);
+        let normalv = Tuple::<f64>::new_vector(0.0, 0.0, -1.0);
+        let light = PointLight::new(
+            Tuple::<f64>::new_point(0.0, 10.0, -10.0),
+            Color::new(1.0, 1.0, 1.0),
+        );
+        let value = 0.1 + 0.9 * FRAC_1_SQRT_2;
+
+        let result = lighting(
+            &material,
+            &Sphere::new(1),
+            &light,
+            position,
+            eyev,
+            normalv,
+            1.0,
+        );
+        assert_eq!(result, Color::new(value, value, value))
+    }
+
+    #[test]
+    fn lighting_eye_in_reflection_path() {
+        let material = Material::<f64>::default();
+        let position = Tuple::<f64>::new_point(0.0, 0.0, 0.0);
+        let eyev = Tuple::<f64>::new_vector(0.0, -FRAC_1_SQRT_2, -FRAC_1_SQRT_2);
+        let normalv = Tuple::<f64>::new_vector(0.0, 0.0, -1.0);
+        let light = PointLight::new(
+            Tuple::<f64>::new_point(0.0, 10.0, -10.0),
+            Color::new(1.0, 1.0, 1.0),
+        );
+        let value = 0.1 + 0.9 * FRAC_1_SQRT_2 + 0.9;
+
+        let result = lighting(
+            &material,
+            &Sphere::new(1),
+            &light,
+            position,
+            eyev,
+            normalv,
+            1.0,
+        );
+        assert_eq!(result, Color::new(value, value, value))
+    }
+
+    #[test]
+    fn lighting_light_behind_surface() {
+        let material = Material::<f64>::default();
+        let position = Tuple::<f64>::new_point(0.0, 0.0, 0.0);
+        let eyev = Tuple::<f64>::new_vector(0.0, 0.0, -1.0);
+        let normalv = Tuple::<f64>::new_vector(0.0, 0.0, -1.0);
+        let light = PointLight::new(
+            Tuple::<f64>::new_point(0.0, 0.0, 10.0),
+            Color::new(1.0, 1.0, 1.0),
+        );
+
+        let result = lighting(
+            &material,
+            &Sphere::new(1),
+            &light,
+            position,
+            eyev,
+            normalv,
+            1.0,
+        );
+        assert_eq!(result, Color::new(0.1, 0.1, 0.1))
+    }
+
+    #[test]
+    fn lighting_surface_in_shadow() {
+        let material = Material::<f64>::default();
+        let position = Tuple::<f64>::new_point(0.0, 0.0, 0.0);
+        let eyev = Tuple::<f64>::new_vector(0.0, 0.0, -1.0);
+        let normalv = Tuple::<f64>::new_vector(0.0, 0.0, -1.0);
+        let light = PointLight::new(
+            Tuple::<f64>::new_point(0.0, 0.0, -10.0),
+            Color::new(1.0, 1.0, 1.0),
+        );
+
+        let result = lighting(
+            &material,
+            &Sphere::new(1),
+            &light,
+            position,
+            eyev,
+            normalv,
+            0.0,
+        );
+        assert_eq!(result, Color::new(0.1, 0.1, 0.1))
+    }
+
+    #[test]
+    fn lighting_with_pattern() {
+        let material = Material::<f64> {
+            pattern: Some(Box::new(StripePattern::new(
+                Color::new(1.0, 1.0, 1.0),
+                Color::new(0.0, 0.0, 0.0),
+            ))),
+            ambient: 1.0,
+            diffuse: 0.0,
+            specular: 0.0,
+            ..Material::default()
+        };
+        let eyev = Tuple::<f64>::new_vector(0.0, 0.0, -1.0);
+        let normalv = Tuple::<f64>::new_vector(0.0, 0.0, -1.0);
+        let light = PointLight::new(
+            Tuple::<f64>::new_point(0.0, 0.0, -10.0),
+            Color::new(1.0, 1.0, 1.0),
+        );
+        let sphere = Sphere::new(1);
+
+        let color_1 = lighting(
+            &material,
+            &sphere,
+            &light,
+            Tuple::new_point(0.9, 0.0, 0.0),
+            eyev,
+            normalv,
+            1.0,
+        );
+        let color_2 = lighting(
+            &material,
+            &sphere,
+            &light,
+            Tuple::new_point(1.1, 0.0, 0.0),
+            eyev,
+            normalv,
+            1.0,
+        );
+        assert_eq!(color_1, Color::new(1.0, 1.0, 1.0));
+        assert_eq!(color_2, Color::new(0.0, 0.0, 0.0))
+    }
+
+    #[test]
+    fn point_light_has_one_sample() {
+        let light = PointLight::new(
+            Tuple::<f64>::new_point(0.0, 0.0, -10.0),
+            Color::new(1.0, 1.0, 1.0),
+        );
+        let samples = light.samples(Tuple::new_point(0.0, 0.0, -1.0));
+
+        assert_eq!(samples.len(), 1);
+        assert_eq!(samples[0].direction, Tuple::new_vector(0.0, 0.0, -1.0));
+        assert_eq!(samples[0].distance, 9.0);
+        assert_eq!(samples[0].intensity, Color::new(1.0, 1.0, 1.0))
+    }
+
+    #[test]
+    fn lighting_scales_with_visible_light() {
+        let material = Material::<f64> {
+            ambient: 0.1,
+            diffuse: 0.9,
+            specular: 0.0,
+            ..Material::default()
+        };
+        let point = Tuple::<f64>::new_point(0.0, 0.0, -1.0);
+        let eyev = Tuple::<f64>::new_vector(0.0, 0.0, -1.0);
+        let normalv = Tuple::<f64>::new_vector(0.0, 0.0, -1.0);
+        let light = PointLight::new(
+            Tuple::<f64>::new_point(0.0, 0.0, -10.0),
+            Color::new(1.0, 1.0, 1.0),
+        );
+        let parameters = [(1.0, 1.0), (0.5, 0.55), (0.0, 0.1)];
+
+        for (visible, value) in parameters {
+            let result = lighting(
+                &material,
+                &Sphere::new(1),
+                &light,
+                point,
+                eyev,
+                normalv,
+                visible,
+            );
+            assert_eq!(result, Color::new(value, value, value))
+        }
+    }
+
+    #[test]
+    fn lighting_averages_samples_of_area_light() {
+        let material = Material::<f64> {
+            ambient: 0.1,
+            diffuse: 0.9,
+            specular: 0.0,
+            ..Material::default()
+        };
+        let sphere = Sphere::new(1);
+        let point = Tuple::<f64>::new_point(0.0, 0.0, -1.0);
+        let eyev = Tuple::<f64>::new_vector(0.0, 0.0, -1.0);
+        let normalv = Tuple::<f64>::new_vector(0.0, 0.0, -1.0);
+        let light = AreaLight::new(
+            Tuple::new_point(-0.5, -0.5, -5.0),
+            Tuple::new_vector(1.0, 0.0, 0.0),
+            2,
+            Tuple::new_vector(0.0, 1.0, 0.0),
+            2,
+            Color::new(1.0, 1.0, 1.0),
+        );
+        let ambient = Color::new(0.1, 0.1, 0.1);
+        let expected =
+            light
+                .sample_positions(point)
+                .into_iter()
+                .fold(ambient.clone(), |sum, position| {
+                    let point_light = PointLight::new(position, Color::new(1.0, 1.0, 1.0));
+                    let color =
+                        lighting(&material, &sphere, &point_light, point, eyev, normalv, 1.0);
+                    sum + (color - ambient.clone()) * 0.25
+                });
+
+        assert_eq!(
+            lighting(&material, &sphere, &light, point, eyev, normalv, 1.0),
+            expected
+        );
+        assert_eq!(
+            lighting(&material, &sphere, &light, point, eyev, normalv, 0.5),
+            ambient.clone() + (expected - ambient) * 0.5
+        )
+    }
+}
@@ -1,83 +1,130 @@
 #![warn(rust_2018_idioms)]
 
-use crate::canvas::Canvas;
-use crate::color::Color;
-use crate::intersection::{Intersection, IntersectionObject, Intersections};
-use crate::matrix::Matrix;
-use crate::projectile::{Environment, Projectile};
-use crate::ray::Ray;
-use crate::sphere::Sphere;
-use crate::tuple::Tuple;
 use num::ToPrimitive;
-use rayon::iter::*;
-use rayon::prelude;
+use raytracer::camera::Camera;
+use raytracer::canvas::Canvas;
+use raytracer::color::Color;
+use raytracer::light::PointLight;
+use raytracer::material::Material;
+use raytracer::plane::Plane;
+use raytracer::projectile::{Environment, Projectile};
+use raytracer::shape::Shape;
+use raytracer::sphere::Sphere;
+use raytracer::world::World;
+use raytracer::Matrix;
+use raytracer::Tuple;
 use std::f64::consts::PI;
-use std::sync::Mutex;
-
-mod canvas;
-mod color;
-mod float_service;
-mod intersection;
-mod matrix;
-mod projectile;
-mod ray;
-mod sphere;
-mod tuple;
-
-/**
+
+/*
    Author: Maciek Mika
    This is the main file that runs the ray tracer
 */
 
 const CANVAS_WIDTH: usize = 800;
 const CANVAS_HEIGHT: usize = 800;
-const FILE_NAME: &str = "intersection.ppm";
+const FILE_NAME: &str = "scene.ppm";
 
 fn main() {
     //write_projectile();
     //write_clock();
-    cast_rays_at_sphere()
+    render_scene()
 }
 
-fn cast_rays_at_sphere() {
-    let origin = Tuple::<f64>::new_point(0.0, 0.0, -5.0);
-    let wall_z = 10.0;
-    let wall_size = 7.0;
-    let half = wall_size / 2.0;
-    let canvas_pixel = 100;
-    let pixel_size = wall_size / canvas_pixel.to_f64().unwrap();
-    let canvas = Mutex::new(Canvas::new(canvas_pixel, canvas_pixel));
-    let color = Color::new(1.0, 0.0, 0.0);
-    let sphere = Sphere::<f64>::new(1);
-
-    (0..canvas_pixel).par_bridge().for_each(|y| {
-        let world_y = half - pixel_size * y.to_f64().unwrap();
-
-        for x in 0..canvas_pixel {
-            let world_x = -half + pixel_size * x.to_f64().unwrap();
-            let position = Tuple::<f64>::new_point(world_x, world_y, wall_z);
-            let ray = Ray::new(origin, (position - origin).normalize());
-            match sphere.intersect(ray) {
-                Ok(intersections) => match intersections.hit() {
-                    None => {}
-                    Some(_) => {
-                        let mut canvas = canvas.lock().unwrap();
-                        match canvas.write_pixel(x, y, color.clone()) {
-                            Ok(_) => {}
-                            Err(_) => {}
-                        }
-                    }
-                },
-                Err(_) => {}
-            }
-        }
+// render a small scene of three spheres standing on a floor in
+// front of two walls, the walls are flattened spheres
+fn render_scene() {
+    let mut world = World::new();
+
+    let wall_material = Material {
+        color: Color::new(1.0, 0.9, 0.9),
+        specular: 0.0,
+        ..Material::default()
+    };
+    let mut floor = Plane::<f64>::new(1);
+    floor.set_material(wall_material.clone());
+
+    let mut left_wall = Sphere::<f64>::new(2);
+    left_wall
+        .set_transformation(
+            Matrix::translation(0.0, 0.0, 5.0)
+                * Matrix::rotate_y(-PI / 4.0)
+                * Matrix::rotate_x(PI / 2.0)
+                * Matrix::scaling(10.0, 0.01, 10.0),
+        )
+        .unwrap();
+    left_wall.set_material(wall_material.clone());
+
+    let mut right_wall = Sphere::<f64>::new(3);
+    right_wall
+        .set_transformation(
+            Matrix::translation(0.0, 0.0, 5.0)
+                * Matrix::rotate_y(PI / 4.0)
+                * Matrix::rotate_x(PI / 2.0)
+                * Matrix::scaling(10.0, 0.01, 10.0),
+        )
+        .unwrap();
+    right_wall.set_material(wall_material);
+
+    let mut middle = Sphere::<f64>::new(4);
+    middle
+        .set_transformation(Matrix::translation(-0.5, 1.0, 0.5))
+        .unwrap();
+    middle.set_material(Material {
+        color: Color::new(0.1, 1.0, 0.5),
+        diffuse: 0.7,
+        specular: 0.3,
+        ..Material::default()
+    });
+
+    let mut right = Sphere::<f64>::new(5);
+    right
+        .set_transformation(Matrix::translation(1.5, 0.5, -0.5) * Matrix::scaling(0.5, 0.5, 0.5))
+        .unwrap();
+    right.set_material(Material {
+        color: Color::new(0.5, 1.0, 0.1),
+        diffuse: 0.7,
+        specular: 0.3,
+        ..Material::default()
+    });
+
+    let mut left = Sphere::<f64>::new(6);
+    left.set_transformation(
+        Matrix::translation(-1.5, 0.33, -0.75) * Matrix::scaling(0.33, 0.33, 0.33),
+    )
+    .unwrap();
+    left.set_material(Material {
+        color: Color::new(1.0, 0.8, 0.1),
+        diffuse: 0.7,
+        specular: 0.3,
+        ..Material::default()
     });
 
-    canvas.lock().unwrap().write_ppm(FILE_NAME);
+    world.add_object(Box::new(floor));
+    world.add_object(Box::new(left_wall));
+    world.add_object(Box::new(right_wall));
+    world.add_object(Box::new(middle));
+    world.add_object(Box::new(right));
+    world.add_object(Box::new(left));
+    world.add_light(Box::new(PointLight::new(
+        Tuple::new_point(-10.0, 10.0, -10.0),
+        Color::new(1.0, 1.0, 1.0),
+    )));
+
+    let mut camera = Camera::new(CANVAS_WIDTH / 4, CANVAS_HEIGHT / 8, PI / 3.0);
+    camera
+        .set_transformation(Matrix::view_transform(
+            Tuple::new_point(0.0, 1.5, -5.0),
+            Tuple::new_point(0.0, 1.0, 0.0),
+            Tuple::new_vector(0.0, 1.0, 0.0),
+        ))
+        .unwrap();
+
+    camera.render(&world).write_ppm(FILE_NAME);
 }
 
 // we use isize because the point coordinates can become negative but by adding
 // the center y and center x pixels raise it back to a positive value
+#[allow(dead_code)]
 fn write_clock() {
     let mut point = Tuple::new_point(0.0, 0.0, 1.0);
     let mut canvas = Canvas::new_with_color(CANVAS_WIDTH, CANVAS_HEIGHT, Color::new(0.0, 0.0, 0.0));
@@ -114,6 +161,7 @@ fn write_clock() {
     canvas.write_ppm("clock.ppm")
 }
 
+#[allow(dead_code)]
 fn write_projectile() {
     let start = Tuple::new_point(0.0, 1.0, 0.0);
     let velocity = Tuple::new_vector(1.0, 1.8, 0.0).normalize() * 11.25;
@@ -0,0 +1,122 @@
+use crate::color::Color;
+use crate::pattern::Pattern;
+use num::Float;
+
+/*
+    This file contains the material struct. A material describes how the
+    surface of a shape reacts to light using the Phong reflection model
+*/
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Material<T>
+where
+    T: Float,
+{
+    pub color: Color,
+    // when a pattern is set it is used instead of the color
+    pub pattern: Option<Box<dyn Pattern<T>>>,
+    pub ambient: T,
+    pub diffuse: T,
+    pub specular: T,
+    pub shininess: T,
+    // how much of the surrounding scene is mirrored, 0 is not
+    // reflective at all and 1 is a perfect mirror
+    pub reflective: T,
+    // how much light passes through the surface, 0 is opaque
+    pub transparency: T,
+    // how much light bends when it enters the material,
+    // 1 is a vacuum and 1.5 is glass
+    pub refractive_index: T,
+}
+
+impl<T> Material<T>
+where
+    T: Float,
+{
+    // create a new material with the given attributes
+    pub fn new(color: Color, ambient: T, diffuse: T, specular: T, shininess: T) -> Self {
+        Self {
+            color,
+            pattern: None,
+            ambient,
+            diffuse,
+            specular,
+            shininess,
+            reflective: T::zero(),
+            transparency: T::zero(),
+            refractive_index: T::one(),
+        }
+    }
+}
+
+// the default material is white with the values
+// used throughout the book
+impl<T> Default for Material<T>
+where
+    T: Float,
+{
+    fn default() -> Self {
+        Self {
+            color: Color::new(1.0, 1.0, 1.0),
+            pattern: None,
+            ambient: T::from(0.1).unwrap(),
+            diffuse: T::from(0.9).unwrap(),
+            specular: T::from(0.9).unwrap(),
+            shininess: T::from(200.0).unwrap(),
+            reflective: T::zero(),
+            transparency: T::zero(),
+            refractive_index: T::one(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::color::Color;
+    use crate::material::Material;
+    use crate::pattern::{Pattern, StripePattern};
+
+    #[test]
+    fn default_material() {
+        let material = Material::<f64>::default();
+
+        assert_eq!(material.color, Color::new(1.0, 1.0, 1.0));
+        assert!(material.pattern.is_none());
+        assert_eq!(material.ambient, 0.1);
+        assert_eq!(material.diffuse, 0.9);
+        assert_eq!(material.specular, 0.9);
+        assert_eq!(material.shininess, 200.0);
+        assert_eq!(material.reflective, 0.0);
+        assert_eq!(material.transparency, 0.0);
+        assert_eq!(material.refractive_index, 1.0)
+    }
+
+    #[test]
+    fn create_material() {
+        let material = Material::<f64>::new(Color::new(1.0, 0.0, 0.0), 0.2, 0.7, 0.3, 50.0);
+
+        assert_eq!(material.color, Color::new(1.0, 0.0, 0.0));
+        assert_eq!(material.ambient, 0.2);
+        assert_eq!(material.diffuse, 0.7);
+        assert_eq!(material.specular, 0.3);
+        assert_eq!(material.shininess, 50.0)
+    }
+
+    #[test]
+    fn material_with_pattern_equals_its_clone() {
+        let stripes = |a: Color| -> Box<dyn Pattern<f64>> {
+            Box::new(StripePattern::new(a, Color::new(0.0, 0.0, 0.0)))
+        };
+        let material = Material {
+            pattern: Some(stripes(Color::new(1.0, 1.0, 1.0))),
+            ..Material::default()
+        };
+        let other = Material {
+            pattern: Some(stripes(Color::new(0.5, 0.5, 0.5))),
+            ..Material::default()
+        };
+
+        assert_eq!(material, material.clone());
+        assert_ne!(material, other)
+    }
+}
@@ -17,7 +17,9 @@ impl<T, const N: usize> Matrix<T, N>
 where
     T: Float,
 {
-    // create a new matrix with given size
+    // create a new matrix with given size. there is no Default because
+    // a matrix filled with zeros is not a useful transformation
+    #[allow(clippy::new_without_default)]
     pub fn new() -> Self {
         Self {
             data: [[T::zero(); N]; N],
@@ -209,6 +211,22 @@ where
 
         matrix
     }
+
+    // create the transformation that orients the world relative to
+    // an eye at `from` looking at `to`, with `up` roughly pointing upwards
+    pub fn view_transform(from: Tuple<T>, to: Tuple<T>, up: Tuple<T>) -> Self {
+        let forward = (to - from).normalize();
+        let left = forward.cross_product(&up.normalize());
+        let true_up = left.cross_product(&forward);
+        let orientation = Self::from(vec![
+            vec![left.x, left.y, left.z, T::zero()],
+            vec![true_up.x, true_up.y, true_up.z, T::zero()],
+            vec![-forward.x, -forward.y, -forward.z, T::zero()],
+            vec![T::zero(), T::zero(), T::zero(), T::one()],
+        ]);
+
+        orientation * Self::translation(-from.x, -from.y, -from.z)
+    }
 }
 
 // functions for Matrix with a constant size of 3x3
@@ -299,15 +317,10 @@ where
 {
     fn from_iter<T: IntoIterator<Item = Vec<A>>>(iter: T) -> Self {
         let mut matrix: Matrix<A, N> = Matrix::new();
-        let mut matrix_row = 0;
-
-        for row in iter {
-            let mut matrix_col = 0;
-            for col in row {
+        for (matrix_row, row) in iter.into_iter().enumerate() {
+            for (matrix_col, col) in row.into_iter().enumerate() {
                 matrix[matrix_row][matrix_col] = col;
-                matrix_col += 1
             }
-            matrix_row += 1
         }
         matrix
     }
@@ -706,7 +719,7 @@ mod tests {
         ]);
 
         assert_eq!(matrix.determinant(), -2120.0);
-        assert_eq!(matrix.invertible(), true)
+        assert!(matrix.invertible())
     }
 
     #[test]
@@ -719,7 +732,7 @@ mod tests {
         ]);
 
         assert_eq!(matrix.determinant(), 0.0);
-        assert_eq!(matrix.invertible(), false)
+        assert!(!matrix.invertible())
     }
 
     #[test]
@@ -868,7 +881,7 @@ mod tests {
     fn translation_does_not_effect_vectors() {
         let transform = Matrix::<f64, 4>::translation(5.0, -3.0, 2.0);
         let vector = Tuple::<f64>::new_vector(-3.0, 4.0, 5.0);
-        let correct_vector = vector.clone();
+        let correct_vector = vector;
 
         assert_eq!(transform * vector, correct_vector)
     }
@@ -925,7 +938,7 @@ mod tests {
         let half_quarter = Matrix::<f64, 4>::rotate_x(PI / 4.0);
         let full_quarter = Matrix::<f64, 4>::rotate_x(PI / 2.0);
 
-        assert_eq!(half_quarter * point.clone(), correct_half_quarter);
+        assert_eq!(half_quarter * point, correct_half_quarter);
         assert_eq!(full_quarter * point, correct_full_quarter)
     }
 
@@ -942,7 +955,7 @@ mod tests {
                 assert_eq!(true, false)
             }
         }
-        assert_eq!(half_quarter * point.clone(), correct_half_quarter);
+        assert_eq!(half_quarter * point, correct_half_quarter);
     }
 
     #[test]
@@ -955,7 +968,7 @@ mod tests {
         let half_quarter = Matrix::<f64, 4>::rotate_y(PI / 4.0);
         let full_quarter = Matrix::<f64, 4>::rotate_y(PI / 2.0);
 
-        assert_eq!(half_quarter * point.clone(), correct_half_quarter);
+        assert_eq!(half_quarter * point, correct_half_quarter);
         assert_eq!(full_quarter * point, correct_full_quarter)
     }
 
@@ -969,7 +982,7 @@ mod tests {
         let half_quarter = Matrix::<f64, 4>::rotate_z(PI / 4.0);
         let full_quarter = Matrix::<f64, 4>::rotate_z(PI / 2.0);
 
-        assert_eq!(half_quarter * point.clone(), correct_half_quarter);
+        assert_eq!(half_quarter * point, correct_half_quarter);
         assert_eq!(full_quarter * point, correct_full_quarter)
     }
 
@@ -1054,4 +1067,60 @@ mod tests {
 
         assert_eq!(transform_c * transform_b * transform_a * point_1, point_2)
     }
+
+    #[test]
+    fn view_transform_default_orientation() {
+        let from = Tuple::<f64>::new_point(0.0, 0.0, 0.0);
+        let to = Tuple::<f64>::new_point(0.0, 0.0, -1.0);
+        let up = Tuple::<f64>::new_vector(0.0, 1.0, 0.0);
+
+        assert_eq!(
+            Matrix::view_transform(from, to, up),
+            Matrix::identity_matrix()
+        )
+    }
+
+    #[test]
+    fn view_transform_positive_z() {
+        let from = Tuple::<f64>::new_point(0.0, 0.0, 0.0);
+        let to = Tuple::<f64>::new_point(0.0, 0.0, 1.0);
+        let up = Tuple::<f64>::new_vector(0.0, 1.0, 0.0);
+
+        assert_eq!(
+            Matrix::view_transform(from, to, up),
+            Matrix::scaling(-1.0, 1.0, -1.0)
+        )
+    }
+
+    #[test]
+    fn view_transform_moves_world() {
+        let from = Tuple::<f64>::new_point(0.0, 0.0, 8.0);
+        let to = Tuple::<f64>::new_point(0.0, 0.0, 0.0);
+        let up = Tuple::<f64>::new_vector(0.0, 1.0, 0.0);
+
+        assert_eq!(
+            Matrix::view_transform(from, to, up),
+            Matrix::translation(0.0, 0.0, -8.0)
+        )
+    }
+
+    #[test]
+    fn view_transform_arbitrary() {
+        let from = Tuple::<f64>::new_point(1.0, 3.0, 2.0);
+        let to = Tuple::<f64>::new_point(4.0, -2.0, 8.0);
+        let up = Tuple::<f64>::new_vector(1.0, 1.0, 0.0);
+        let correct_matrix = Matrix::<f64, 4>::from(vec![
+            vec![-0.50709, 0.50709, 0.67612, -2.36643],
+            vec![0.76772, 0.60609, 0.12122, -2.82843],
+            vec![-0.35857, 0.59761, -0.71714, 0.00000],
+            vec![0.00000, 0.00000, 0.00000, 1.00000],
+        ]);
+        let transform = Matrix::view_transform(from, to, up);
+
+        for row in 0..4 {
+            for col in 0..4 {
+                assert!((transform[row][col] - correct_matrix[row][col]).abs() < 0.0001)
+            }
+        }
+    }
 }
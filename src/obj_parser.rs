@@ -0,0 +1,554 @@
+use crate::group::Group;
+use crate::shape::Shape;
+use crate::triangle::{SmoothTriangle, Triangle};
+use crate::Tuple;
+use num::Float;
+use std::fs;
+use std::ops::AddAssign;
+
+/*
+    This file contains the parser for Wavefront OBJ files. Faces are turned
+    into triangles, polygons are split into a fan of triangles. Lines that
+    are not supported or can not be read are skipped and remembered with
+    their line number so a broken file still loads as much as possible
+*/
+
+// the corners of a face, every corner has a vertex and maybe a normal
+type Corner<T> = (Tuple<T>, Option<Tuple<T>>);
+
+// the name of a group and the triangles that belong to it
+type NamedGroup<T> = (String, Vec<Box<dyn Shape<T>>>);
+
+pub struct ObjParser<T>
+where
+    T: Float,
+    T: AddAssign,
+{
+    // malformed lines are kept as None, so they still take up their index
+    // and the faces after them refer to the right elements
+    pub vertices: Vec<Option<Tuple<T>>>,
+    pub normals: Vec<Option<Tuple<T>>>,
+    pub texture_vertices: Vec<Option<(T, T)>>,
+    // triangles that are defined before the first group statement
+    pub default_group: Vec<Box<dyn Shape<T>>>,
+    // named groups in the order they first appear in the file
+    pub groups: Vec<NamedGroup<T>>,
+    // line number and reason of every line that was skipped
+    pub ignored: Vec<(usize, &'static str)>,
+    current_group: Option<usize>,
+    next_id: i32,
+}
+
+impl<T> ObjParser<T>
+where
+    T: Float,
+    T: AddAssign,
+    T: Send + Sync + 'static,
+{
+    // parse the content of an obj file, the triangles
+    // are given ids starting at 1 in the order they are read
+    pub fn parse(content: &str) -> Self {
+        let mut parser = Self {
+            vertices: vec![],
+            normals: vec![],
+            texture_vertices: vec![],
+            default_group: vec![],
+            groups: vec![],
+            ignored: vec![],
+            current_group: None,
+            next_id: 1,
+        };
+
+        for (index, line) in content.lines().enumerate() {
+            if let Err(reason) = parser.parse_line(line) {
+                parser.ignored.push((index + 1, reason))
+            }
+        }
+        parser
+    }
+
+    // read the obj file at the given path and parse it
+    pub fn parse_file(file_name: &str) -> Result<Self, &'static str> {
+        match fs::read_to_string(file_name) {
+            Ok(content) => Ok(Self::parse(&content)),
+            Err(_) => Err("reading from file failed"),
+        }
+    }
+
+    // return the triangles of the group with the given name
+    pub fn group(&self, name: &str) -> Option<&Vec<Box<dyn Shape<T>>>> {
+        self.groups
+            .iter()
+            .find(|(group_name, _)| group_name == name)
+            .map(|(_, shapes)| shapes)
+    }
+
+    // collect the triangles into a group that can be placed in a world,
+    // every named group becomes a group of its own inside of it. the named
+    // groups get the ids that follow the ids of the triangles
+    pub fn into_group(self, id: i32) -> Group<T> {
+        let mut group = Group::new(id);
+        self.default_group
+            .into_iter()
+            .for_each(|triangle| group.add_child(triangle));
+
+        for (index, (_, triangles)) in self.groups.into_iter().enumerate() {
+            let mut named_group = Group::new(self.next_id + index as i32);
+            triangles
+                .into_iter()
+                .for_each(|triangle| named_group.add_child(triangle));
+            group.add_child(Box::new(named_group))
+        }
+        group
+    }
+
+    fn parse_line(&mut self, line: &str) -> Result<(), &'static str> {
+        let mut tokens = line.split_whitespace();
+        let statement = match tokens.next() {
+            // empty lines and comments are not reported
+            None => return Ok(()),
+            Some(token) if token.starts_with('#') => return Ok(()),
+            Some(token) => token,
+        };
+        let arguments: Vec<&str> = tokens.collect();
+
+        match statement {
+            "v" => {
+                let vertex = parse_numbers(&arguments, "malformed vertex")
+                    .map(|[x, y, z]| Tuple::new_point(x, y, z));
+                self.vertices.push(vertex.ok());
+                vertex?;
+            }
+            "vn" => {
+                let normal = parse_numbers(&arguments, "malformed vertex normal")
+                    .map(|[x, y, z]| Tuple::new_vector(x, y, z));
+                self.normals.push(normal.ok());
+                normal?;
+            }
+            "vt" => {
+                let texture_vertex = parse_texture_vertex(&arguments);
+                self.texture_vertices.push(texture_vertex.ok());
+                texture_vertex?;
+            }
+            "f" => self.parse_face(&arguments)?,
+            "g" | "o" => match arguments.first() {
+                None => return Err("group without a name"),
+                Some(name) => self.select_group(name),
+            },
+            _ => return Err("unsupported statement"),
+        }
+        Ok(())
+    }
+
+    // switch to the group with the given name, the group
+    // is created when it does not exist yet
+    fn select_group(&mut self, name: &str) {
+        let index = match self.groups.iter().position(|(group, _)| group == name) {
+            Some(index) => index,
+            None => {
+                self.groups.push((name.to_string(), vec![]));
+                self.groups.len() - 1
+            }
+        };
+        self.current_group = Some(index)
+    }
+
+    // a face is split into a fan of triangles around its first corner. when
+    // every corner has a normal smooth triangles are created
+    fn parse_face(&mut self, arguments: &[&str]) -> Result<(), &'static str> {
+        if arguments.len() < 3 {
+            return Err("face with less than three vertices");
+        }
+        let corners = arguments
+            .iter()
+            .map(|argument| self.parse_corner(argument))
+            .collect::<Result<Vec<Corner<T>>, &'static str>>()?;
+        let smooth = corners.iter().all(|(_, normal)| normal.is_some());
+
+        // a fan triangle whose corners lie on a line is skipped, the
+        // face is only rejected when none of its triangles has an area
+        let mut triangles: Vec<Box<dyn Shape<T>>> = vec![];
+        for index in 1..corners.len() - 1 {
+            let (p1, n1) = corners[0];
+            let (p2, n2) = corners[index];
+            let (p3, n3) = corners[index + 1];
+            let triangle: Result<Box<dyn Shape<T>>, &'static str> = match (smooth, n1, n2, n3) {
+                (true, Some(n1), Some(n2), Some(n3)) => {
+                    SmoothTriangle::new(self.next_id, (p1, p2, p3), (n1, n2, n3))
+                        .map(|triangle| Box::new(triangle) as Box<dyn Shape<T>>)
+                }
+                _ => Triangle::new(self.next_id, p1, p2, p3)
+                    .map(|triangle| Box::new(triangle) as Box<dyn Shape<T>>),
+            };
+            if let Ok(triangle) = triangle {
+                self.next_id += 1;
+                triangles.push(triangle)
+            }
+        }
+        if triangles.is_empty() {
+            return Err("face is degenerate");
+        }
+
+        match self.current_group {
+            None => self.default_group.extend(triangles),
+            Some(group) => self.groups[group].1.extend(triangles),
+        }
+        Ok(())
+    }
+
+    // read a corner in the form v, v/vt, v//vn or v/vt/vn
+    fn parse_corner(&self, argument: &str) -> Result<Corner<T>, &'static str> {
+        let mut indices = argument.split('/');
+        let vertex = match indices
+            .next()
+            .and_then(|index| resolve_index(index, self.vertices.len()))
+        {
+            None => return Err("face references a missing vertex"),
+            Some(index) => self.vertices[index].ok_or("face references a malformed vertex")?,
+        };
+
+        if let Some(index) = indices.next().filter(|index| !index.is_empty()) {
+            match resolve_index(index, self.texture_vertices.len()) {
+                None => return Err("face references a missing texture vertex"),
+                Some(index) => self.texture_vertices[index]
+                    .map(|_| ())
+                    .ok_or("face references a malformed texture vertex")?,
+            }
+        }
+
+        let normal = match indices.next().filter(|index| !index.is_empty()) {
+            None => None,
+            Some(index) => match resolve_index(index, self.normals.len()) {
+                None => return Err("face references a missing vertex normal"),
+                Some(index) => {
+                    Some(self.normals[index].ok_or("face references a malformed vertex normal")?)
+                }
+            },
+        };
+        Ok((vertex, normal))
+    }
+}
+
+// read the first N arguments as numbers, extra arguments like
+// the optional w of a vertex are allowed and skipped
+fn parse_numbers<T, const N: usize>(
+    arguments: &[&str],
+    error: &'static str,
+) -> Result<[T; N], &'static str>
+where
+    T: Float,
+{
+    let mut numbers = [T::zero(); N];
+    if arguments.len() < N {
+        return Err(error);
+    }
+    for (number, argument) in numbers.iter_mut().zip(arguments) {
+        *number = parse_number(argument).ok_or(error)?;
+    }
+    Ok(numbers)
+}
+
+// read a texture vertex, only u is required. v is 0 when it is
+// missing and the optional w is checked but not kept
+fn parse_texture_vertex<T>(arguments: &[&str]) -> Result<(T, T), &'static str>
+where
+    T: Float,
+{
+    let error = "malformed texture vertex";
+    if arguments.is_empty() || arguments.len() > 3 {
+        return Err(error);
+    }
+    let numbers = arguments
+        .iter()
+        .map(|argument| parse_number(argument).ok_or(error))
+        .collect::<Result<Vec<T>, &'static str>>()?;
+    Ok((numbers[0], numbers.get(1).copied().unwrap_or(T::zero())))
+}
+
+// read a single number, nan and infinity are parsed by rust
+// but can not be used as coordinates so they are rejected
+fn parse_number<T>(argument: &str) -> Option<T>
+where
+    T: Float,
+{
+    argument
+        .parse::<f64>()
+        .ok()
+        .filter(|number| number.is_finite())
+        .and_then(T::from)
+}
+
+// indices in an obj file start at 1, negative indices
+// count back from the last element that was read
+fn resolve_index(index: &str, count: usize) -> Option<usize> {
+    let index = index.parse::<isize>().ok()?;
+    let resolved = match index {
+        0 => return None,
+        i if i > 0 => i - 1,
+        i => count as isize + i,
+    };
+
+    match resolved >= 0 && (resolved as usize) < count {
+        true => Some(resolved as usize),
+        false => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::obj_parser::ObjParser;
+    use crate::ray::Ray;
+    use crate::shape::Shape;
+    use crate::Tuple;
+
+    #[test]
+    fn ignore_unrecognized_lines() {
+        let content = "There was a young lady named Bright\n\
+                       who traveled much faster than light.\n\
+                       \n\
+                       # comments are not reported\n\
+                       She set out one day";
+        let parser = ObjParser::<f64>::parse(content);
+
+        assert_eq!(parser.ignored.len(), 3);
+        assert_eq!(
+            parser
+                .ignored
+                .iter()
+                .map(|(line, _)| *line)
+                .collect::<Vec<usize>>(),
+            vec![1, 2, 5]
+        )
+    }
+
+    #[test]
+    fn parse_vertex_records() {
+        let content = "v -1 1 0\nv -1.0000 0.5000 0.0000\nv 1 0 0\nv 1 1 0";
+        let parser = ObjParser::<f64>::parse(content);
+
+        assert_eq!(parser.vertices[0], Some(Tuple::new_point(-1.0, 1.0, 0.0)));
+        assert_eq!(parser.vertices[1], Some(Tuple::new_point(-1.0, 0.5, 0.0)));
+        assert_eq!(parser.vertices[2], Some(Tuple::new_point(1.0, 0.0, 0.0)));
+        assert_eq!(parser.vertices[3], Some(Tuple::new_point(1.0, 1.0, 0.0)))
+    }
+
+    #[test]
+    fn parse_vertex_normal_records() {
+        let content = "vn 0 0 1\nvn 0.707 0 -0.707\nvn 1 2 3";
+        let parser = ObjParser::<f64>::parse(content);
+
+        assert_eq!(parser.normals[0], Some(Tuple::new_vector(0.0, 0.0, 1.0)));
+        assert_eq!(
+            parser.normals[1],
+            Some(Tuple::new_vector(0.707, 0.0, -0.707))
+        );
+        assert_eq!(parser.normals[2], Some(Tuple::new_vector(1.0, 2.0, 3.0)))
+    }
+
+    #[test]
+    fn parse_triangle_faces() {
+        let content = "v -1 1 0\nv -1 0 0\nv 1 0 0\nv 1 1 0\n\nf 1 2 3\nf 1 3 4";
+        let parser = ObjParser::<f64>::parse(content);
+
+        assert!(parser.ignored.is_empty());
+        assert_eq!(parser.default_group.len(), 2);
+        // the triangles are checked by the rays that hit them
+        let ray = Ray::new(
+            Tuple::new_point(-0.5, 0.25, -5.0),
+            Tuple::new_vector(0.0, 0.0, 1.0),
+        );
+        assert_eq!(parser.default_group[0].intersect(ray).len(), 1);
+        assert!(parser.default_group[1].intersect(ray).is_empty())
+    }
+
+    #[test]
+    fn triangulate_polygons() {
+        let content = "v -1 1 0\nv -1 0 0\nv 1 0 0\nv 1 1 0\nv 0 2 0\n\nf 1 2 3 4 5";
+        let parser = ObjParser::<f64>::parse(content);
+        let ids: Vec<i32> = parser.default_group.iter().map(|s| s.id()).collect();
+
+        assert_eq!(parser.default_group.len(), 3);
+        assert_eq!(ids, vec![1, 2, 3])
+    }
+
+    #[test]
+    fn triangles_in_groups() {
+        let content = "v -1 1 0\nv -1 0 0\nv 1 0 0\nv 1 1 0\n\
+                       g FirstGroup\nf 1 2 3\n\
+                       o SecondGroup\nf 1 3 4\n\
+                       g FirstGroup\nf 2 3 4";
+        let parser = ObjParser::<f64>::parse(content);
+
+        assert!(parser.default_group.is_empty());
+        assert_eq!(parser.groups.len(), 2);
+        assert_eq!(parser.group("FirstGroup").unwrap().len(), 2);
+        assert_eq!(parser.group("SecondGroup").unwrap().len(), 1);
+        assert!(parser.group("ThirdGroup").is_none())
+    }
+
+    #[test]
+    fn convert_obj_to_group() {
+        let content = "v -1 1 0\nv -1 0 0\nv 1 0 0\nv 1 1 0\n\
+                       f 1 2 3\n\
+                       g FirstGroup\nf 1 2 3\n\
+                       g SecondGroup\nf 1 3 4";
+        let group = ObjParser::<f64>::parse(content).into_group(10);
+        let ids: Vec<i32> = group.children().iter().map(|s| s.id()).collect();
+
+        assert_eq!(group.id(), 10);
+        assert_eq!(ids, vec![1, 4, 5])
+    }
+
+    #[test]
+    fn parse_texture_vertex_records() {
+        let content = "vt 0.5\nvt 0.25 0.75\nvt 0.1 0.2 0.3\nvt\nvt 1 2 3 4\n\
+                       v 0 1 0\nv -1 0 0\nv 1 0 0\n\
+                       f 1/1 2/2 3/3";
+        let parser = ObjParser::<f64>::parse(content);
+
+        assert_eq!(
+            parser.texture_vertices,
+            vec![
+                Some((0.5, 0.0)),
+                Some((0.25, 0.75)),
+                Some((0.1, 0.2)),
+                None,
+                None
+            ]
+        );
+        assert_eq!(
+            parser.ignored,
+            vec![
+                (4, "malformed texture vertex"),
+                (5, "malformed texture vertex")
+            ]
+        );
+        assert_eq!(parser.default_group.len(), 1)
+    }
+
+    #[test]
+    fn reject_numbers_that_are_not_finite() {
+        let content = "v nan 0 0\nv 0 inf 0\nv 0 0 -inf\nvn NaN 0 1\nvt infinity\n\
+                       v 0 1 0\nv -1 0 0\nv 1 0 0\n\
+                       f 1 5 6\n\
+                       f 4 5 6";
+        let parser = ObjParser::<f64>::parse(content);
+
+        assert_eq!(
+            parser.ignored,
+            vec![
+                (1, "malformed vertex"),
+                (2, "malformed vertex"),
+                (3, "malformed vertex"),
+                (4, "malformed vertex normal"),
+                (5, "malformed texture vertex"),
+                (9, "face references a malformed vertex"),
+            ]
+        );
+        assert_eq!(parser.default_group.len(), 1)
+    }
+
+    #[test]
+    fn faces_with_normals_are_smooth() {
+        let content = "v 0 1 0\nv -1 0 0\nv 1 0 0\n\
+                       vn -1 0 0\nvn 1 0 0\nvn 0 1 0\n\
+                       vt 0 0\n\
+                       f 1//3 2//1 3//2\n\
+                       f 1/1/3 2/1/1 3/1/2\n\
+                       f 1/1 2/1 3/1";
+        let parser = ObjParser::<f64>::parse(content);
+        let ray = Ray::new(
+            Tuple::new_point(-0.2, 0.3, -2.0),
+            Tuple::new_vector(0.0, 0.0, 1.0),
+        );
+
+        assert!(parser.ignored.is_empty());
+        assert_eq!(parser.default_group.len(), 3);
+        for (index, triangle) in parser.default_group.iter().enumerate() {
+            let hit = triangle.intersect(ray)[0];
+            let normal = triangle.normal_at(ray.position(hit.value), &hit);
+            let expected = match index {
+                // the corner normals are interpolated for smooth triangles
+                0 | 1 => Tuple::new_vector(-0.5547, 0.83205, 0.0),
+                _ => Tuple::new_vector(0.0, 0.0, -1.0),
+            };
+
+            assert_eq!(normal, expected)
+        }
+    }
+
+    #[test]
+    fn negative_indices_count_from_the_end() {
+        let content = "v -1 1 0\nv -1 0 0\nv 1 0 0\nf -3 -2 -1";
+        let parser = ObjParser::<f64>::parse(content);
+        let ray = Ray::new(
+            Tuple::new_point(-0.5, 0.5, -5.0),
+            Tuple::new_vector(0.0, 0.0, 1.0),
+        );
+
+        assert_eq!(parser.default_group[0].intersect(ray).len(), 1)
+    }
+
+    #[test]
+    fn report_malformed_lines() {
+        let content = "v 1 0\nv 0 1 0\nv -1 0 0\nv 1 0 0\n\
+                       vn a b c\n\
+                       f 2 3\n\
+                       f 2 3 9\n\
+                       f 2//4 3 4\n\
+                       f 1 2 3\n\
+                       f 2//1 3//1 4//1\n\
+                       g\n\
+                       f 2 3 4";
+        let parser = ObjParser::<f64>::parse(content);
+
+        assert_eq!(
+            parser.ignored,
+            vec![
+                (1, "malformed vertex"),
+                (5, "malformed vertex normal"),
+                (6, "face with less than three vertices"),
+                (7, "face references a missing vertex"),
+                (8, "face references a missing vertex normal"),
+                (9, "face references a malformed vertex"),
+                (10, "face references a malformed vertex normal"),
+                (11, "group without a name"),
+            ]
+        );
+        assert_eq!(parser.vertices.len(), 4);
+        assert_eq!(parser.normals, vec![None]);
+        assert_eq!(parser.default_group.len(), 1)
+    }
+
+    #[test]
+    fn malformed_vertex_keeps_its_index() {
+        let content = "v 1 0\nv 0 0 0\nv 1 0 0\nv 0 1 0\nf 2 3 4";
+        let parser = ObjParser::<f64>::parse(content);
+        let ray = Ray::new(
+            Tuple::new_point(0.25, 0.25, -1.0),
+            Tuple::new_vector(0.0, 0.0, 1.0),
+        );
+
+        assert_eq!(parser.ignored, vec![(1, "malformed vertex")]);
+        assert_eq!(parser.vertices[0], None);
+        assert_eq!(parser.vertices[1], Some(Tuple::new_point(0.0, 0.0, 0.0)));
+        assert_eq!(parser.default_group.len(), 1);
+        assert_eq!(parser.default_group[0].intersect(ray).len(), 1)
+    }
+
+    #[test]
+    fn skip_degenerate_triangles() {
+        let content = "v 0 0 0\nv 1 0 0\nv 2 0 0\nv 0 1 0\n\
+                       f 1 2 3\n\
+                       f 1 2 3 4";
+        let parser = ObjParser::<f64>::parse(content);
+
+        assert_eq!(parser.ignored, vec![(5, "face is degenerate")]);
+        assert_eq!(parser.default_group.len(), 1)
+    }
+
+    #[test]
+    fn parse_missing_file() {
+        assert!(ObjParser::<f64>::parse_file("does_not_exist.obj").is_err())
+    }
+}
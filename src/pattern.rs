@@ -0,0 +1,910 @@
+use crate::color::Color;
+use crate::perlin::noise;
+use crate::shape::Shape;
+use crate::{Matrix, Tuple};
+use num::Float;
+use std::any::Any;
+use std::fmt::{Debug, Formatter};
+use std::ops::AddAssign;
+
+/*
+    This file contains the pattern trait and the basic patterns. A pattern
+    gives a material a color that changes over the surface of a shape. The
+    point of a hit is moved from world space to object space and then into
+    the space of the pattern, so a pattern moves along with its shape
+*/
+
+// properties that every pattern has, the inverse of the
+// transformation is cached because every hit needs it
+#[derive(Debug, Clone, PartialEq)]
+pub struct PatternProperties<T>
+where
+    T: Float,
+{
+    transformation: Matrix<T, 4>,
+    inverse: Matrix<T, 4>,
+}
+
+impl<T> Default for PatternProperties<T>
+where
+    T: Float,
+    T: AddAssign,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> PatternProperties<T>
+where
+    T: Float,
+    T: AddAssign,
+{
+    // create properties with the identity transformation
+    pub fn new() -> Self {
+        Self {
+            transformation: Matrix::identity_matrix(),
+            inverse: Matrix::identity_matrix(),
+        }
+    }
+}
+
+// lets materials with a pattern be cloned, see ShapeClone
+pub trait PatternClone<T>
+where
+    T: Float,
+{
+    fn clone_box(&self) -> Box<dyn Pattern<T>>;
+}
+
+impl<T, P> PatternClone<T> for P
+where
+    T: Float,
+    P: Pattern<T> + Clone + 'static,
+{
+    fn clone_box(&self) -> Box<dyn Pattern<T>> {
+        Box::new(self.clone())
+    }
+}
+
+impl<T> Clone for Box<dyn Pattern<T>>
+where
+    T: Float,
+{
+    fn clone(&self) -> Self {
+        self.clone_box()
+    }
+}
+
+// patterns are not Debug, so only the transformation is shown
+impl<T> Debug for dyn Pattern<T>
+where
+    T: Float,
+    T: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Pattern")
+            .field("transformation", self.transformation())
+            .finish()
+    }
+}
+
+// patterns are compared by their contents, two patterns are equal when
+// they have the same type and equal fields. every pattern that is
+// PartialEq gets this, which lets materials with a pattern be compared
+pub trait PatternEq<T>
+where
+    T: Float,
+{
+    fn as_any(&self) -> &dyn Any;
+
+    fn eq_dyn(&self, other: &dyn Pattern<T>) -> bool;
+}
+
+impl<T, P> PatternEq<T> for P
+where
+    T: Float,
+    P: Pattern<T> + PartialEq + 'static,
+{
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn eq_dyn(&self, other: &dyn Pattern<T>) -> bool {
+        match other.as_any().downcast_ref::<P>() {
+            Some(other) => self == other,
+            None => false,
+        }
+    }
+}
+
+impl<T> PartialEq for dyn Pattern<T>
+where
+    T: Float,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.eq_dyn(other)
+    }
+}
+
+// the Pattern trait is object safe so a material can hold any
+// pattern as Box<dyn Pattern<T>>
+pub trait Pattern<T>: Send + Sync + PatternClone<T> + PatternEq<T>
+where
+    T: Float,
+{
+    fn properties(&self) -> &PatternProperties<T>;
+
+    fn properties_mut(&mut self) -> &mut PatternProperties<T>;
+
+    // return the color at a point that is in pattern space
+    fn local_color_at(&self, point: Tuple<T>) -> Color;
+
+    fn transformation(&self) -> &Matrix<T, 4> {
+        &self.properties().transformation
+    }
+
+    fn inverse(&self) -> &Matrix<T, 4> {
+        &self.properties().inverse
+    }
+
+    // change the transformation of the pattern, fails when
+    // the transformation can not be inverted
+    fn set_transformation(&mut self, transformation: Matrix<T, 4>) -> Result<(), &'static str>
+    where
+        T: AddAssign,
+    {
+        let inverse = match transformation.inverse() {
+            Ok(inv) => inv,
+            Err(_) => return Err("transformation is not invertible"),
+        };
+        let properties = self.properties_mut();
+        properties.transformation = transformation;
+        properties.inverse = inverse;
+        Ok(())
+    }
+
+    // return the color at a point in object space
+    fn color_at(&self, object_point: Tuple<T>) -> Color
+    where
+        T: AddAssign,
+    {
+        self.local_color_at(self.inverse().clone() * object_point)
+    }
+
+    // return the color at a point in world space on the given object
+    fn color_at_object(&self, object: &dyn Shape<T>, world_point: Tuple<T>) -> Color
+    where
+        T: AddAssign,
+    {
+        self.color_at(object.world_to_object(world_point))
+    }
+}
+
+// return true when the value falls in an even band of width one
+fn is_even<T>(value: T) -> bool
+where
+    T: Float,
+{
+    (value.floor() % T::from(2.0).unwrap()) == T::zero()
+}
+
+// a single color everywhere, this is how the other patterns
+// turn their colors into patterns
+#[derive(Debug, Clone, PartialEq)]
+pub struct SolidPattern<T>
+where
+    T: Float,
+{
+    properties: PatternProperties<T>,
+    pub color: Color,
+}
+
+impl<T> SolidPattern<T>
+where
+    T: Float,
+    T: AddAssign,
+{
+    pub fn new(color: Color) -> Self {
+        Self {
+            properties: PatternProperties::new(),
+            color,
+        }
+    }
+}
+
+impl<T> Pattern<T> for SolidPattern<T>
+where
+    T: Float,
+    T: Send + Sync + 'static,
+{
+    fn properties(&self) -> &PatternProperties<T> {
+        &self.properties
+    }
+
+    fn properties_mut(&mut self) -> &mut PatternProperties<T> {
+        &mut self.properties
+    }
+
+    fn local_color_at(&self, _point: Tuple<T>) -> Color {
+        self.color.clone()
+    }
+}
+
+// stripes that alternate along the x axis. the stripes, like the
+// parts of the other patterns, can be colors or patterns themselves
+#[derive(Debug, Clone)]
+pub struct StripePattern<T>
+where
+    T: Float,
+{
+    properties: PatternProperties<T>,
+    pub a: Box<dyn Pattern<T>>,
+    pub b: Box<dyn Pattern<T>>,
+}
+
+impl<T> StripePattern<T>
+where
+    T: Float,
+    T: AddAssign,
+    T: Send + Sync + 'static,
+{
+    pub fn new(a: Color, b: Color) -> Self {
+        Self::new_nested(
+            Box::new(SolidPattern::new(a)),
+            Box::new(SolidPattern::new(b)),
+        )
+    }
+
+    // use two patterns instead of two colors
+    pub fn new_nested(a: Box<dyn Pattern<T>>, b: Box<dyn Pattern<T>>) -> Self {
+        Self {
+            properties: PatternProperties::new(),
+            a,
+            b,
+        }
+    }
+}
+
+// derive can not compare the boxed parts, so they are compared by hand
+impl<T> PartialEq for StripePattern<T>
+where
+    T: Float,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.properties == other.properties && *self.a == *other.a && *self.b == *other.b
+    }
+}
+
+impl<T> Pattern<T> for StripePattern<T>
+where
+    T: Float,
+    T: AddAssign,
+    T: Send + Sync + 'static,
+{
+    fn properties(&self) -> &PatternProperties<T> {
+        &self.properties
+    }
+
+    fn properties_mut(&mut self) -> &mut PatternProperties<T> {
+        &mut self.properties
+    }
+
+    fn local_color_at(&self, point: Tuple<T>) -> Color {
+        match is_even(point.x) {
+            true => self.a.color_at(point),
+            false => self.b.color_at(point),
+        }
+    }
+}
+
+// blends from the first color to the second color between
+// x = 0 and x = 1, the blend repeats every unit
+#[derive(Debug, Clone)]
+pub struct GradientPattern<T>
+where
+    T: Float,
+{
+    properties: PatternProperties<T>,
+    pub a: Box<dyn Pattern<T>>,
+    pub b: Box<dyn Pattern<T>>,
+}
+
+impl<T> GradientPattern<T>
+where
+    T: Float,
+    T: AddAssign,
+    T: Send + Sync + 'static,
+{
+    pub fn new(a: Color, b: Color) -> Self {
+        Self::new_nested(
+            Box::new(SolidPattern::new(a)),
+            Box::new(SolidPattern::new(b)),
+        )
+    }
+
+    // use two patterns instead of two colors
+    pub fn new_nested(a: Box<dyn Pattern<T>>, b: Box<dyn Pattern<T>>) -> Self {
+        Self {
+            properties: PatternProperties::new(),
+            a,
+            b,
+        }
+    }
+}
+
+impl<T> PartialEq for GradientPattern<T>
+where
+    T: Float,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.properties == other.properties && *self.a == *other.a && *self.b == *other.b
+    }
+}
+
+impl<T> Pattern<T> for GradientPattern<T>
+where
+    T: Float,
+    T: AddAssign,
+    T: Send + Sync + 'static,
+{
+    fn properties(&self) -> &PatternProperties<T> {
+        &self.properties
+    }
+
+    fn properties_mut(&mut self) -> &mut PatternProperties<T> {
+        &mut self.properties
+    }
+
+    fn local_color_at(&self, point: Tuple<T>) -> Color {
+        let fraction = (point.x - point.x.floor()).to_f64().unwrap();
+        let a = self.a.color_at(point);
+        a.clone() + (self.b.color_at(point) - a) * fraction
+    }
+}
+
+// rings of two colors around the y axis
+#[derive(Debug, Clone)]
+pub struct RingPattern<T>
+where
+    T: Float,
+{
+    properties: PatternProperties<T>,
+    pub a: Box<dyn Pattern<T>>,
+    pub b: Box<dyn Pattern<T>>,
+}
+
+impl<T> RingPattern<T>
+where
+    T: Float,
+    T: AddAssign,
+    T: Send + Sync + 'static,
+{
+    pub fn new(a: Color, b: Color) -> Self {
+        Self::new_nested(
+            Box::new(SolidPattern::new(a)),
+            Box::new(SolidPattern::new(b)),
+        )
+    }
+
+    // use two patterns instead of two colors
+    pub fn new_nested(a: Box<dyn Pattern<T>>, b: Box<dyn Pattern<T>>) -> Self {
+        Self {
+            properties: PatternProperties::new(),
+            a,
+            b,
+        }
+    }
+}
+
+impl<T> PartialEq for RingPattern<T>
+where
+    T: Float,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.properties == other.properties && *self.a == *other.a && *self.b == *other.b
+    }
+}
+
+impl<T> Pattern<T> for RingPattern<T>
+where
+    T: Float,
+    T: AddAssign,
+    T: Send + Sync + 'static,
+{
+    fn properties(&self) -> &PatternProperties<T> {
+        &self.properties
+    }
+
+    fn properties_mut(&mut self) -> &mut PatternProperties<T> {
+        &mut self.properties
+    }
+
+    fn local_color_at(&self, point: Tuple<T>) -> Color {
+        match is_even((point.x.powi(2) + point.z.powi(2)).sqrt()) {
+            true => self.a.color_at(point),
+            false => self.b.color_at(point),
+        }
+    }
+}
+
+// cubes of two colors that alternate in all three dimensions
+#[derive(Debug, Clone)]
+pub struct CheckerPattern<T>
+where
+    T: Float,
+{
+    properties: PatternProperties<T>,
+    pub a: Box<dyn Pattern<T>>,
+    pub b: Box<dyn Pattern<T>>,
+}
+
+impl<T> CheckerPattern<T>
+where
+    T: Float,
+    T: AddAssign,
+    T: Send + Sync + 'static,
+{
+    pub fn new(a: Color, b: Color) -> Self {
+        Self::new_nested(
+            Box::new(SolidPattern::new(a)),
+            Box::new(SolidPattern::new(b)),
+        )
+    }
+
+    // use two patterns instead of two colors
+    pub fn new_nested(a: Box<dyn Pattern<T>>, b: Box<dyn Pattern<T>>) -> Self {
+        Self {
+            properties: PatternProperties::new(),
+            a,
+            b,
+        }
+    }
+}
+
+impl<T> PartialEq for CheckerPattern<T>
+where
+    T: Float,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.properties == other.properties && *self.a == *other.a && *self.b == *other.b
+    }
+}
+
+impl<T> Pattern<T> for CheckerPattern<T>
+where
+    T: Float,
+    T: AddAssign,
+    T: Send + Sync + 'static,
+{
+    fn properties(&self) -> &PatternProperties<T> {
+        &self.properties
+    }
+
+    fn properties_mut(&mut self) -> &mut PatternProperties<T> {
+        &mut self.properties
+    }
+
+    fn local_color_at(&self, point: Tuple<T>) -> Color {
+        match is_even(point.x.floor() + point.y.floor() + point.z.floor()) {
+            true => self.a.color_at(point),
+            false => self.b.color_at(point),
+        }
+    }
+}
+
+// the average of two patterns, both are seen at the same time
+#[derive(Debug, Clone)]
+pub struct BlendPattern<T>
+where
+    T: Float,
+{
+    properties: PatternProperties<T>,
+    pub a: Box<dyn Pattern<T>>,
+    pub b: Box<dyn Pattern<T>>,
+}
+
+impl<T> BlendPattern<T>
+where
+    T: Float,
+    T: AddAssign,
+{
+    pub fn new(a: Box<dyn Pattern<T>>, b: Box<dyn Pattern<T>>) -> Self {
+        Self {
+            properties: PatternProperties::new(),
+            a,
+            b,
+        }
+    }
+}
+
+impl<T> PartialEq for BlendPattern<T>
+where
+    T: Float,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.properties == other.properties && *self.a == *other.a && *self.b == *other.b
+    }
+}
+
+impl<T> Pattern<T> for BlendPattern<T>
+where
+    T: Float,
+    T: AddAssign,
+    T: Send + Sync + 'static,
+{
+    fn properties(&self) -> &PatternProperties<T> {
+        &self.properties
+    }
+
+    fn properties_mut(&mut self) -> &mut PatternProperties<T> {
+        &mut self.properties
+    }
+
+    fn local_color_at(&self, point: Tuple<T>) -> Color {
+        (self.a.color_at(point) + self.b.color_at(point)) * 0.5
+    }
+}
+
+// moves the point by perlin noise before the inner pattern is
+// looked up, this makes straight stripes and rings look like
+// marble and wood. the scale is the largest distance a point moves
+#[derive(Debug, Clone)]
+pub struct PerturbedPattern<T>
+where
+    T: Float,
+{
+    properties: PatternProperties<T>,
+    pub pattern: Box<dyn Pattern<T>>,
+    pub scale: T,
+}
+
+impl<T> PerturbedPattern<T>
+where
+    T: Float,
+    T: AddAssign,
+{
+    pub fn new(pattern: Box<dyn Pattern<T>>, scale: T) -> Self {
+        Self {
+            properties: PatternProperties::new(),
+            pattern,
+            scale,
+        }
+    }
+}
+
+impl<T> PartialEq for PerturbedPattern<T>
+where
+    T: Float,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.properties == other.properties
+            && *self.pattern == *other.pattern
+            && self.scale == other.scale
+    }
+}
+
+impl<T> Pattern<T> for PerturbedPattern<T>
+where
+    T: Float,
+    T: AddAssign,
+    T: Send + Sync + 'static,
+{
+    fn properties(&self) -> &PatternProperties<T> {
+        &self.properties
+    }
+
+    fn properties_mut(&mut self) -> &mut PatternProperties<T> {
+        &mut self.properties
+    }
+
+    // the noise for y and z is taken further along the z axis, so
+    // every axis moves by a different amount
+    fn local_color_at(&self, point: Tuple<T>) -> Color {
+        let offset = T::from(10.5).unwrap();
+        let (x, y, z) = (point.x, point.y, point.z);
+        let jitter = Tuple::new_vector(
+            noise(x, y, z),
+            noise(x, y, z + offset),
+            noise(x, y, z + offset + offset),
+        );
+        self.pattern.color_at(point + jitter * self.scale)
+    }
+}
+
+// pattern that returns the point it is given as a color, the tests
+// use it to check in which space a pattern is evaluated
+#[cfg(test)]
+#[derive(Clone, PartialEq)]
+pub(crate) struct TestPattern {
+    properties: PatternProperties<f64>,
+}
+
+#[cfg(test)]
+impl Pattern<f64> for TestPattern {
+    fn properties(&self) -> &PatternProperties<f64> {
+        &self.properties
+    }
+
+    fn properties_mut(&mut self) -> &mut PatternProperties<f64> {
+        &mut self.properties
+    }
+
+    fn local_color_at(&self, point: Tuple<f64>) -> Color {
+        Color::new(point.x, point.y, point.z)
+    }
+}
+
+#[cfg(test)]
+pub(crate) fn test_pattern() -> TestPattern {
+    TestPattern {
+        properties: PatternProperties::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::color::Color;
+    use crate::pattern::{
+        test_pattern, BlendPattern, CheckerPattern, GradientPattern, Pattern, PerturbedPattern,
+        RingPattern, SolidPattern, StripePattern,
+    };
+    use crate::shape::Shape;
+    use crate::sphere::Sphere;
+    use crate::{Matrix, Tuple};
+
+    fn white() -> Color {
+        Color::new(1.0, 1.0, 1.0)
+    }
+
+    fn black() -> Color {
+        Color::new(0.0, 0.0, 0.0)
+    }
+
+    #[test]
+    fn default_pattern_transformation() {
+        let pattern = test_pattern();
+
+        assert_eq!(*pattern.transformation(), Matrix::identity_matrix())
+    }
+
+    #[test]
+    fn assign_pattern_transformation() {
+        let mut pattern = test_pattern();
+        pattern
+            .set_transformation(Matrix::translation(1.0, 2.0, 3.0))
+            .unwrap();
+
+        assert_eq!(
+            *pattern.transformation(),
+            Matrix::translation(1.0, 2.0, 3.0)
+        );
+        assert!(pattern
+            .set_transformation(Matrix::scaling(0.0, 0.0, 0.0))
+            .is_err())
+    }
+
+    #[test]
+    fn pattern_with_object_transformation() {
+        let mut sphere = Sphere::new(1);
+        sphere
+            .set_transformation(Matrix::scaling(2.0, 2.0, 2.0))
+            .unwrap();
+        let color = test_pattern().color_at_object(&sphere, Tuple::new_point(2.0, 3.0, 4.0));
+
+        assert_eq!(color, Color::new(1.0, 1.5, 2.0))
+    }
+
+    #[test]
+    fn pattern_with_pattern_transformation() {
+        let sphere = Sphere::new(1);
+        let mut pattern = test_pattern();
+        pattern
+            .set_transformation(Matrix::scaling(2.0, 2.0, 2.0))
+            .unwrap();
+        let color = pattern.color_at_object(&sphere, Tuple::new_point(2.0, 3.0, 4.0));
+
+        assert_eq!(color, Color::new(1.0, 1.5, 2.0))
+    }
+
+    #[test]
+    fn pattern_with_object_and_pattern_transformation() {
+        let mut sphere = Sphere::new(1);
+        sphere
+            .set_transformation(Matrix::scaling(2.0, 2.0, 2.0))
+            .unwrap();
+        let mut pattern = test_pattern();
+        pattern
+            .set_transformation(Matrix::translation(0.5, 1.0, 1.5))
+            .unwrap();
+        let color = pattern.color_at_object(&sphere, Tuple::new_point(2.5, 3.0, 3.5));
+
+        assert_eq!(color, Color::new(0.75, 0.5, 0.25))
+    }
+
+    #[test]
+    fn stripe_pattern_alternates_in_x() {
+        let pattern = StripePattern::<f64>::new(white(), black());
+        let parameters = [
+            ((0.0, 0.0, 0.0), white()),
+            ((0.0, 1.0, 0.0), white()),
+            ((0.0, 0.0, 2.0), white()),
+            ((0.9, 0.0, 0.0), white()),
+            ((1.0, 0.0, 0.0), black()),
+            ((-0.1, 0.0, 0.0), black()),
+            ((-1.0, 0.0, 0.0), black()),
+            ((-1.1, 0.0, 0.0), white()),
+        ];
+
+        for ((x, y, z), color) in parameters {
+            assert_eq!(pattern.local_color_at(Tuple::new_point(x, y, z)), color)
+        }
+    }
+
+    #[test]
+    fn gradient_interpolates_between_colors() {
+        let pattern = GradientPattern::<f64>::new(white(), black());
+        let parameters = [(0.0, 1.0), (0.25, 0.75), (0.5, 0.5), (0.75, 0.25)];
+
+        for (x, value) in parameters {
+            assert_eq!(
+                pattern.local_color_at(Tuple::new_point(x, 0.0, 0.0)),
+                Color::new(value, value, value)
+            )
+        }
+    }
+
+    #[test]
+    fn ring_extends_in_x_and_z() {
+        let pattern = RingPattern::<f64>::new(white(), black());
+        let parameters = [
+            ((0.0, 0.0, 0.0), white()),
+            ((1.0, 0.0, 0.0), black()),
+            ((0.0, 0.0, 1.0), black()),
+            ((0.708, 0.0, 0.708), black()),
+        ];
+
+        for ((x, y, z), color) in parameters {
+            assert_eq!(pattern.local_color_at(Tuple::new_point(x, y, z)), color)
+        }
+    }
+
+    #[test]
+    fn checkers_repeat_in_every_dimension() {
+        let pattern = CheckerPattern::<f64>::new(white(), black());
+        let parameters = [
+            ((0.0, 0.0, 0.0), white()),
+            ((0.99, 0.0, 0.0), white()),
+            ((1.01, 0.0, 0.0), black()),
+            ((0.0, 0.99, 0.0), white()),
+            ((0.0, 1.01, 0.0), black()),
+            ((0.0, 0.0, 0.99), white()),
+            ((0.0, 0.0, 1.01), black()),
+        ];
+
+        for ((x, y, z), color) in parameters {
+            assert_eq!(pattern.local_color_at(Tuple::new_point(x, y, z)), color)
+        }
+    }
+
+    #[test]
+    fn clone_boxed_pattern() {
+        let mut pattern = StripePattern::<f64>::new(white(), black());
+        pattern
+            .set_transformation(Matrix::translation(1.0, 0.0, 0.0))
+            .unwrap();
+        let boxed: Box<dyn Pattern<f64>> = Box::new(pattern);
+        let cloned = boxed.clone();
+
+        assert_eq!(cloned.transformation(), boxed.transformation());
+        assert!(*cloned == *boxed)
+    }
+
+    #[test]
+    fn compare_patterns_by_contents() {
+        let stripes: Box<dyn Pattern<f64>> = Box::new(StripePattern::new(white(), black()));
+        let parameters: [(Box<dyn Pattern<f64>>, bool); 4] = [
+            (Box::new(StripePattern::new(white(), black())), true),
+            (Box::new(StripePattern::new(black(), white())), false),
+            (Box::new(RingPattern::new(white(), black())), false),
+            (Box::new(SolidPattern::new(white())), false),
+        ];
+
+        for (pattern, equal) in parameters {
+            assert_eq!(*pattern == *stripes, equal)
+        }
+    }
+
+    #[test]
+    fn solid_pattern_is_one_color() {
+        let pattern = SolidPattern::<f64>::new(white());
+
+        assert_eq!(
+            pattern.local_color_at(Tuple::new_point(0.0, 0.0, 0.0)),
+            white()
+        );
+        assert_eq!(
+            pattern.local_color_at(Tuple::new_point(-3.5, 2.0, 10.0)),
+            white()
+        )
+    }
+
+    #[test]
+    fn checkers_of_stripes() {
+        let red = Color::new(1.0, 0.0, 0.0);
+        let mut stripes = StripePattern::<f64>::new(red.clone(), white());
+        stripes
+            .set_transformation(Matrix::scaling(0.5, 0.5, 0.5))
+            .unwrap();
+        let pattern =
+            CheckerPattern::new_nested(Box::new(stripes), Box::new(SolidPattern::new(black())));
+        let parameters = [
+            ((0.25, 0.0, 0.0), red.clone()),
+            ((0.75, 0.0, 0.0), white()),
+            ((0.25, 0.0, 0.5), red),
+            ((1.25, 0.0, 0.0), black()),
+            ((0.25, 1.5, 0.0), black()),
+        ];
+
+        for ((x, y, z), color) in parameters {
+            assert_eq!(pattern.local_color_at(Tuple::new_point(x, y, z)), color)
+        }
+    }
+
+    #[test]
+    fn nested_pattern_uses_space_of_parent() {
+        let mut pattern = StripePattern::new_nested(
+            Box::new(test_pattern()),
+            Box::new(SolidPattern::new(black())),
+        );
+        pattern
+            .set_transformation(Matrix::scaling(2.0, 2.0, 2.0))
+            .unwrap();
+
+        assert_eq!(
+            pattern.color_at(Tuple::new_point(1.0, 2.0, 3.0)),
+            Color::new(0.5, 1.0, 1.5)
+        )
+    }
+
+    #[test]
+    fn blend_averages_two_patterns() {
+        let mut rotated = StripePattern::new(white(), black());
+        rotated
+            .set_transformation(Matrix::rotate_y(std::f64::consts::FRAC_PI_2))
+            .unwrap();
+        let pattern = BlendPattern::new(
+            Box::new(StripePattern::new(white(), black())),
+            Box::new(rotated),
+        );
+        let grey = Color::new(0.5, 0.5, 0.5);
+        let parameters = [
+            ((0.5, 0.0, -0.5), white()),
+            ((1.5, 0.0, -0.5), grey.clone()),
+            ((0.5, 0.0, 0.5), grey),
+            ((1.5, 0.0, 0.5), black()),
+        ];
+
+        for ((x, y, z), color) in parameters {
+            assert_eq!(pattern.local_color_at(Tuple::new_point(x, y, z)), color)
+        }
+    }
+
+    #[test]
+    fn perturbed_pattern_without_scale_is_unchanged() {
+        let pattern = PerturbedPattern::new(Box::new(test_pattern()), 0.0);
+        let point = Tuple::new_point(0.3, 1.7, -2.2);
+
+        assert_eq!(pattern.local_color_at(point), Color::new(0.3, 1.7, -2.2))
+    }
+
+    #[test]
+    fn perturbed_pattern_moves_point() {
+        let pattern = PerturbedPattern::new(Box::new(test_pattern()), 0.2);
+
+        assert_ne!(
+            pattern.local_color_at(Tuple::new_point(0.3, 1.7, -2.2)),
+            Color::new(0.3, 1.7, -2.2)
+        )
+    }
+}
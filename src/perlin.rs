@@ -0,0 +1,199 @@
+use num::Float;
+
+/*
+    This file contains 3D Perlin noise, following the improved noise of
+    Ken Perlin. The noise is smooth, returns values between -1 and 1 and
+    is zero at every integer point. It is used to perturb patterns
+*/
+
+// the permutation from the reference implementation, every
+// value from 0 to 255 appears exactly once
+const PERMUTATION: [usize; 256] = [
+    151, 160, 137, 91, 90, 15, 131, 13, 201, 95, 96, 53, 194, 233, 7, 225, 140, 36, 103, 30, 69,
+    142, 8, 99, 37, 240, 21, 10, 23, 190, 6, 148, 247, 120, 234, 75, 0, 26, 197, 62, 94, 252, 219,
+    203, 117, 35, 11, 32, 57, 177, 33, 88, 237, 149, 56, 87, 174, 20, 125, 136, 171, 168, 68, 175,
+    74, 165, 71, 134, 139, 48, 27, 166, 77, 146, 158, 231, 83, 111, 229, 122, 60, 211, 133, 230,
+    220, 105, 92, 41, 55, 46, 245, 40, 244, 102, 143, 54, 65, 25, 63, 161, 1, 216, 80, 73, 209, 76,
+    132, 187, 208, 89, 18, 169, 200, 196, 135, 130, 116, 188, 159, 86, 164, 100, 109, 198, 173,
+    186, 3, 64, 52, 217, 226, 250, 124, 123, 5, 202, 38, 147, 118, 126, 255, 82, 85, 212, 207, 206,
+    59, 227, 47, 16, 58, 17, 182, 189, 28, 42, 223, 183, 170, 213, 119, 248, 152, 2, 44, 154, 163,
+    70, 221, 153, 101, 155, 167, 43, 172, 9, 129, 22, 39, 253, 19, 98, 108, 110, 79, 113, 224, 232,
+    178, 185, 112, 104, 218, 246, 97, 228, 251, 34, 242, 193, 238, 210, 144, 12, 191, 179, 162,
+    241, 81, 51, 145, 235, 249, 14, 239, 107, 49, 192, 214, 31, 181, 199, 106, 157, 184, 84, 204,
+    176, 115, 121, 50, 45, 127, 4, 150, 254, 138, 236, 205, 93, 222, 114, 67, 29, 24, 72, 243, 141,
+    128, 195, 78, 66, 215, 61, 156, 180,
+];
+
+// look up the permutation, the index wraps around so
+// the table does not have to be stored twice
+fn permutation(index: usize) -> usize {
+    PERMUTATION[index & 255]
+}
+
+// smooth the fraction so the noise has no visible edges
+// at the integer points: 6t^5 - 15t^4 + 10t^3
+fn fade<T>(t: T) -> T
+where
+    T: Float,
+{
+    let c = |value: f64| T::from(value).unwrap();
+    t * t * t * (t * (t * c(6.0) - c(15.0)) + c(10.0))
+}
+
+fn lerp<T>(t: T, a: T, b: T) -> T
+where
+    T: Float,
+{
+    a + t * (b - a)
+}
+
+// the dot product of the point with one of twelve gradient
+// directions that is chosen by the lowest four bits of the hash
+fn gradient<T>(hash: usize, x: T, y: T, z: T) -> T
+where
+    T: Float,
+{
+    let h = hash & 15;
+    let u = if h < 8 { x } else { y };
+    let v = match h {
+        0..=3 => y,
+        12 | 14 => x,
+        _ => z,
+    };
+    let u = if h & 1 == 0 { u } else { -u };
+    let v = if h & 2 == 0 { v } else { -v };
+    u + v
+}
+
+// the noise repeats every 256 units, the value is wrapped while it is
+// still a float so huge values can be turned into an index as well.
+// returns None for NaN and infinity
+fn wrap<T>(value: T) -> Option<usize>
+where
+    T: Float,
+{
+    let size = T::from(256.0).unwrap();
+    (value - size * (value / size).floor()).to_usize()
+}
+
+// return the noise value at a point, the value lies between -1 and 1
+pub fn noise<T>(x: T, y: T, z: T) -> T
+where
+    T: Float,
+{
+    let (x_floor, y_floor, z_floor) = (x.floor(), y.floor(), z.floor());
+    // the unit cube that contains the point, there is no noise at
+    // infinity so points that are not finite give zero
+    let (xi, yi, zi) = match (wrap(x_floor), wrap(y_floor), wrap(z_floor)) {
+        (Some(xi), Some(yi), Some(zi)) => (xi, yi, zi),
+        _ => return T::zero(),
+    };
+    // the position of the point inside of the cube
+    let (x, y, z) = (x - x_floor, y - y_floor, z - z_floor);
+    let (u, v, w) = (fade(x), fade(y), fade(z));
+    let one = T::one();
+
+    // hash the eight corners of the cube
+    let a = permutation(xi) + yi;
+    let aa = permutation(a) + zi;
+    let ab = permutation(a + 1) + zi;
+    let b = permutation(xi + 1) + yi;
+    let ba = permutation(b) + zi;
+    let bb = permutation(b + 1) + zi;
+
+    lerp(
+        w,
+        lerp(
+            v,
+            lerp(
+                u,
+                gradient(permutation(aa), x, y, z),
+                gradient(permutation(ba), x - one, y, z),
+            ),
+            lerp(
+                u,
+                gradient(permutation(ab), x, y - one, z),
+                gradient(permutation(bb), x - one, y - one, z),
+            ),
+        ),
+        lerp(
+            v,
+            lerp(
+                u,
+                gradient(permutation(aa + 1), x, y, z - one),
+                gradient(permutation(ba + 1), x - one, y, z - one),
+            ),
+            lerp(
+                u,
+                gradient(permutation(ab + 1), x, y - one, z - one),
+                gradient(permutation(bb + 1), x - one, y - one, z - one),
+            ),
+        ),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::perlin::{noise, PERMUTATION};
+
+    #[test]
+    fn permutation_contains_every_value_once() {
+        let mut sorted = PERMUTATION;
+        sorted.sort();
+
+        for (index, value) in sorted.iter().enumerate() {
+            assert_eq!(index, *value)
+        }
+    }
+
+    #[test]
+    fn noise_is_zero_at_integer_points() {
+        let parameters = [
+            (0.0, 0.0, 0.0),
+            (1.0, 2.0, 3.0),
+            (-4.0, 7.0, -1.0),
+            (300.0, -256.0, 12.0),
+        ];
+
+        for (x, y, z) in parameters {
+            assert_eq!(noise::<f64>(x, y, z), 0.0)
+        }
+    }
+
+    #[test]
+    fn noise_stays_in_range() {
+        for i in 0..1000 {
+            let t = i as f64 * 0.137;
+            let value = noise(t, t * 0.71 - 3.2, 5.3 - t * 1.3);
+
+            assert!((-1.0..=1.0).contains(&value))
+        }
+    }
+
+    #[test]
+    fn noise_is_smooth_and_not_constant() {
+        let value = noise(0.5_f64, 0.25, 0.75);
+        let close = noise(0.5001_f64, 0.25, 0.75);
+
+        assert_ne!(value, 0.0);
+        assert!((value - close).abs() < 0.001);
+        assert_eq!(value, noise(0.5, 0.25, 0.75))
+    }
+
+    #[test]
+    fn noise_of_huge_and_infinite_values() {
+        let parameters = [
+            (1e300, 2.0, 3.0),
+            (-1e20, -1.0, 7.0),
+            (f64::INFINITY, 0.5, 0.5),
+            (0.5, f64::NEG_INFINITY, 0.5),
+            (0.5, 0.5, f64::NAN),
+        ];
+
+        // huge values have no fraction left so they lie on integer points,
+        // there is no noise at infinity
+        for (x, y, z) in parameters {
+            assert_eq!(noise(x, y, z), 0.0)
+        }
+    }
+}
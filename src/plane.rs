@@ -0,0 +1,205 @@
+use crate::bounds::Aabb;
+use crate::intersection::{Intersection, Intersections};
+use crate::ray::Ray;
+use crate::shape::{Shape, ShapeProperties};
+use crate::Tuple;
+use num::Float;
+use std::ops::AddAssign;
+
+// infinite plane that is the xz plane in object space
+#[derive(Clone, PartialEq, Debug)]
+pub struct Plane<T>
+where
+    T: Float,
+{
+    properties: ShapeProperties<T>,
+}
+
+impl<T> Plane<T>
+where
+    T: Float,
+    T: AddAssign,
+{
+    pub fn new(id: i32) -> Self {
+        Self {
+            properties: ShapeProperties::new(id),
+        }
+    }
+}
+
+impl<T> Shape<T> for Plane<T>
+where
+    T: Float,
+    T: AddAssign,
+    T: Send + Sync + 'static,
+{
+    fn properties(&self) -> &ShapeProperties<T> {
+        &self.properties
+    }
+
+    fn properties_mut(&mut self) -> &mut ShapeProperties<T> {
+        &mut self.properties
+    }
+
+    // a ray that is parallel to the plane never hits it, a coplanar
+    // ray is treated the same because the plane is infinitely thin. the
+    // ray is in object space, where it gets shorter as the plane is scaled
+    // up, so only an exactly zero direction counts as parallel
+    fn local_intersect(&self, ray: Ray<T>) -> Intersections<'_, T> {
+        match ray.direction.y == T::zero() {
+            true => Intersections::empty(),
+            false => {
+                let value = -ray.origin.y / ray.direction.y;
+                Intersections::new(vec![Intersection::new(value, self)])
+            }
+        }
+    }
+
+    // the plane points up everywhere
+    fn local_normal_at(&self, _point: Tuple<T>, _hit: &Intersection<'_, T>) -> Tuple<T> {
+        Tuple::new_vector(T::zero(), T::one(), T::zero())
+    }
+
+    fn bounds(&self) -> Aabb<T> {
+        Aabb::new(
+            Tuple::new_point(T::neg_infinity(), T::zero(), T::neg_infinity()),
+            Tuple::new_point(T::infinity(), T::zero(), T::infinity()),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::intersection::Intersection;
+    use crate::plane::Plane;
+    use crate::ray::Ray;
+    use crate::shape::{same_shape, Shape};
+    use crate::{Matrix, Tuple};
+
+    #[test]
+    fn normal_is_constant() {
+        let plane = Plane::<f64>::new(1);
+        let normal = Tuple::new_vector(0.0, 1.0, 0.0);
+
+        assert_eq!(
+            plane.local_normal_at(
+                Tuple::new_point(0.0, 0.0, 0.0),
+                &Intersection::new(0.0, &plane)
+            ),
+            normal
+        );
+        assert_eq!(
+            plane.local_normal_at(
+                Tuple::new_point(10.0, 0.0, -10.0),
+                &Intersection::new(0.0, &plane)
+            ),
+            normal
+        );
+        assert_eq!(
+            plane.local_normal_at(
+                Tuple::new_point(-5.0, 0.0, 150.0),
+                &Intersection::new(0.0, &plane)
+            ),
+            normal
+        )
+    }
+
+    #[test]
+    fn intersect_parallel_ray() {
+        let plane = Plane::<f64>::new(1);
+        let ray = Ray::new(
+            Tuple::new_point(0.0, 10.0, 0.0),
+            Tuple::new_vector(0.0, 0.0, 1.0),
+        );
+
+        assert!(plane.local_intersect(ray).is_empty())
+    }
+
+    #[test]
+    fn intersect_coplanar_ray() {
+        let plane = Plane::<f64>::new(1);
+        let ray = Ray::new(
+            Tuple::new_point(0.0, 0.0, 0.0),
+            Tuple::new_vector(0.0, 0.0, 1.0),
+        );
+
+        assert!(plane.local_intersect(ray).is_empty())
+    }
+
+    #[test]
+    fn intersect_from_above() {
+        let plane = Plane::<f64>::new(1);
+        let ray = Ray::new(
+            Tuple::new_point(0.0, 1.0, 0.0),
+            Tuple::new_vector(0.0, -1.0, 0.0),
+        );
+        let inter = plane.local_intersect(ray);
+
+        assert_eq!(inter.len(), 1);
+        assert_eq!(inter[0].value, 1.0);
+        assert!(same_shape(inter[0].object, &plane))
+    }
+
+    #[test]
+    fn intersect_from_below() {
+        let plane = Plane::<f64>::new(1);
+        let ray = Ray::new(
+            Tuple::new_point(0.0, -1.0, 0.0),
+            Tuple::new_vector(0.0, 1.0, 0.0),
+        );
+        let inter = plane.local_intersect(ray);
+
+        assert_eq!(inter.len(), 1);
+        assert_eq!(inter[0].value, 1.0);
+        assert!(same_shape(inter[0].object, &plane))
+    }
+
+    #[test]
+    fn intersect_transformed_plane() {
+        let mut plane = Plane::<f64>::new(1);
+        plane
+            .set_transformation(Matrix::translation(0.0, -2.0, 0.0))
+            .unwrap();
+        let ray = Ray::new(
+            Tuple::new_point(0.0, 1.0, 0.0),
+            Tuple::new_vector(0.0, -1.0, 0.0),
+        );
+        let inter = plane.intersect(ray);
+
+        assert_eq!(inter.len(), 1);
+        assert_eq!(inter[0].value, 3.0)
+    }
+
+    #[test]
+    fn plane_bounds_are_infinite_except_height() {
+        let bounds = Plane::<f64>::new(1).bounds();
+
+        assert_eq!(bounds.min.x, f64::NEG_INFINITY);
+        assert_eq!(bounds.min.y, 0.0);
+        assert_eq!(bounds.min.z, f64::NEG_INFINITY);
+        assert_eq!(bounds.max.x, f64::INFINITY);
+        assert_eq!(bounds.max.y, 0.0);
+        assert_eq!(bounds.max.z, f64::INFINITY)
+    }
+
+    #[test]
+    fn intersect_large_scaled_plane() {
+        let parameters = [
+            (Matrix::scaling(1e6, 1e6, 1e6), (0.0, -1.0, 0.0), 1.0),
+            (Matrix::identity_matrix(), (0.0, -1e-6, 0.0), 1e6),
+        ];
+
+        for (transformation, (dx, dy, dz), value) in parameters {
+            let mut plane = Plane::<f64>::new(1);
+            plane.set_transformation(transformation).unwrap();
+            let ray = Ray::new(
+                Tuple::new_point(0.0, 1.0, 0.0),
+                Tuple::new_vector(dx, dy, dz),
+            );
+            let inter = plane.intersect(ray);
+
+            assert_eq!(inter.len(), 1);
+            assert!((inter[0].value - value).abs() < 1e-6)
+        }
+    }
+}
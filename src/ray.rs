@@ -7,6 +7,7 @@ use std::ops::AddAssign;
     This file contains the Ray structure and its functions
 */
 
+#[derive(Debug, Clone, Copy)]
 pub struct Ray<T>
 where
     T: Float,
@@ -38,6 +39,7 @@ where
     }
 }
 
+#[cfg(test)]
 mod tests {
     use crate::ray::Ray;
     use crate::{Matrix, Tuple};
@@ -46,7 +48,7 @@ mod tests {
     fn create_ray() {
         let origin = Tuple::<f64>::new_point(1.0, 2.0, 3.0);
         let direction = Tuple::<f64>::new_vector(4.0, 5.0, 6.0);
-        let ray = Ray::<f64>::new(origin.clone(), direction.clone());
+        let ray = Ray::<f64>::new(origin, direction);
         assert_eq!(ray.origin, origin);
         assert_eq!(ray.direction, direction)
     }
@@ -0,0 +1,418 @@
+use crate::bounds::Aabb;
+use crate::intersection::{Intersection, Intersections};
+use crate::material::Material;
+use crate::ray::Ray;
+use crate::{Matrix, Tuple};
+use num::Float;
+use std::ops::AddAssign;
+
+/*
+    This file contains the shape trait that every object in a scene
+    implements. The trait handles the transformation of rays and normals
+    so a shape only has to work in its own object space
+*/
+
+// properties that every shape has, the inverse of the
+// transformation is cached because every ray needs it. the parent
+// inverse is the combined inverse of the groups the shape is in
+#[derive(Debug, Clone, PartialEq)]
+pub struct ShapeProperties<T>
+where
+    T: Float,
+{
+    id: i32,
+    transformation: Matrix<T, 4>,
+    inverse: Matrix<T, 4>,
+    parent_inverse: Matrix<T, 4>,
+    material: Material<T>,
+}
+
+impl<T> ShapeProperties<T>
+where
+    T: Float,
+    T: AddAssign,
+{
+    // create properties with the identity transformation
+    // and the default material
+    pub fn new(id: i32) -> Self {
+        Self {
+            id,
+            transformation: Matrix::<T, 4>::identity_matrix(),
+            inverse: Matrix::<T, 4>::identity_matrix(),
+            parent_inverse: Matrix::<T, 4>::identity_matrix(),
+            material: Material::default(),
+        }
+    }
+}
+
+// boxed shapes can be cloned so a model that is built once can be
+// placed in a scene many times. every shape that is Clone gets this
+// through the blanket impl, boxed patterns are cloned the same way
+pub trait ShapeClone<T>
+where
+    T: Float,
+    T: AddAssign,
+{
+    fn clone_box(&self) -> Box<dyn Shape<T>>;
+}
+
+impl<T, S> ShapeClone<T> for S
+where
+    T: Float,
+    T: AddAssign,
+    S: Shape<T> + Clone + 'static,
+{
+    fn clone_box(&self) -> Box<dyn Shape<T>> {
+        Box::new(self.clone())
+    }
+}
+
+impl<T> Clone for Box<dyn Shape<T>>
+where
+    T: Float,
+    T: AddAssign,
+{
+    fn clone(&self) -> Self {
+        self.clone_box()
+    }
+}
+
+// the Shape trait is object safe so different shapes can be
+// stored together as Box<dyn Shape<T>>. the threads that render the
+// scene share its shapes, patterns and lights, so all of those
+// traits require Send and Sync
+pub trait Shape<T>: Send + Sync + ShapeClone<T>
+where
+    T: Float,
+    T: AddAssign,
+{
+    fn properties(&self) -> &ShapeProperties<T>;
+
+    fn properties_mut(&mut self) -> &mut ShapeProperties<T>;
+
+    // return the intersections of a ray that has already
+    // been transformed into object space
+    fn local_intersect(&self, ray: Ray<T>) -> Intersections<'_, T>;
+
+    // return the normal at a point that is in object space. the hit is
+    // passed along for shapes that interpolate their normal over the
+    // surface, like smooth triangles. shapes that only contain other
+    // shapes, like groups and csg shapes, never end up in an intersection
+    // because the hit always references the child that was hit, so their
+    // normal is never asked for and they can leave this unreachable
+    fn local_normal_at(&self, point: Tuple<T>, hit: &Intersection<'_, T>) -> Tuple<T>;
+
+    // return true when the ray, already in object space, hits the shape
+    // between 0 and max_t. shapes that contain other shapes override this
+    // so they can stop at the first hit instead of sorting every hit
+    fn local_any_hit(&self, ray: Ray<T>, max_t: T) -> bool {
+        self.local_intersect(ray)
+            .iter()
+            .any(|inter| inter.value > T::zero() && inter.value < max_t)
+    }
+
+    // return the box around the shape in object space
+    fn bounds(&self) -> Aabb<T>;
+
+    // return the box around the shape after its own transformation,
+    // which is the space of the group the shape is in
+    fn parent_space_bounds(&self) -> Aabb<T> {
+        self.bounds().transform(self.transformation())
+    }
+
+    fn id(&self) -> i32 {
+        self.properties().id
+    }
+
+    fn transformation(&self) -> &Matrix<T, 4> {
+        &self.properties().transformation
+    }
+
+    fn inverse(&self) -> &Matrix<T, 4> {
+        &self.properties().inverse
+    }
+
+    fn parent_inverse(&self) -> &Matrix<T, 4> {
+        &self.properties().parent_inverse
+    }
+
+    // change the transformation of the shape, fails when
+    // the transformation can not be inverted
+    fn set_transformation(&mut self, transformation: Matrix<T, 4>) -> Result<(), &'static str> {
+        let inverse = match transformation.inverse() {
+            Ok(inv) => inv,
+            Err(_) => return Err("transformation is not invertible"),
+        };
+        let properties = self.properties_mut();
+        properties.transformation = transformation;
+        properties.inverse = inverse;
+        self.update_children();
+        Ok(())
+    }
+
+    // change the combined inverse of the groups the shape is in,
+    // this is done by the group when the shape is added to it
+    fn set_parent_inverse(&mut self, parent_inverse: Matrix<T, 4>) {
+        self.properties_mut().parent_inverse = parent_inverse;
+        self.update_children()
+    }
+
+    // called after the transformation of the shape or one of its parents
+    // changed. shapes that contain other shapes pass the change on
+    fn update_children(&mut self) {}
+
+    // check if the other shape is this shape or one of the shapes it contains
+    fn includes(&self, other: &dyn Shape<T>) -> bool {
+        std::ptr::addr_eq(self, other)
+    }
+
+    fn material(&self) -> &Material<T> {
+        &self.properties().material
+    }
+
+    fn set_material(&mut self, material: Material<T>) {
+        self.properties_mut().material = material
+    }
+
+    // transform the ray into object space and intersect it with the shape
+    fn intersect(&self, ray: Ray<T>) -> Intersections<'_, T> {
+        self.local_intersect(ray.transform(self.inverse().clone()))
+    }
+
+    // transform the ray into object space and check if it hits the shape
+    // between 0 and max_t, which is all a shadow ray needs to know
+    fn any_hit(&self, ray: Ray<T>, max_t: T) -> bool {
+        self.local_any_hit(ray.transform(self.inverse().clone()), max_t)
+    }
+
+    // convert a point in world space to object space by going
+    // through the space of every group the shape is in
+    fn world_to_object(&self, point: Tuple<T>) -> Tuple<T> {
+        self.inverse().clone() * (self.parent_inverse().clone() * point)
+    }
+
+    // convert a normal in object space back to world space with the
+    // transposed inverses, so scaled and sheared shapes keep correct normals
+    fn normal_to_world(&self, normal: Tuple<T>) -> Tuple<T> {
+        let mut world_normal =
+            self.parent_inverse().transpose() * (self.inverse().transpose() * normal);
+        world_normal.w = T::zero();
+
+        world_normal.normalize()
+    }
+
+    // return the normal vector on the shape at the given world point
+    fn normal_at(&self, world_point: Tuple<T>, hit: &Intersection<'_, T>) -> Tuple<T> {
+        let object_point = self.world_to_object(world_point);
+        let object_normal = self.local_normal_at(object_point, hit);
+
+        self.normal_to_world(object_normal)
+    }
+}
+
+// two shapes are the same shape when they are stored at the same address
+pub fn same_shape<T>(shape_1: &dyn Shape<T>, shape_2: &dyn Shape<T>) -> bool
+where
+    T: Float,
+    T: AddAssign,
+{
+    std::ptr::addr_eq(shape_1, shape_2)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bounds::Aabb;
+    use crate::intersection::{Intersection, Intersections};
+    use crate::material::Material;
+    use crate::ray::Ray;
+    use crate::shape::{same_shape, Shape, ShapeProperties};
+    use crate::{Matrix, Tuple};
+    use std::f64::consts::{FRAC_1_SQRT_2, PI};
+    use std::sync::Mutex;
+
+    // shape that remembers the last ray it received in object space
+    struct TestShape {
+        properties: ShapeProperties<f64>,
+        saved_ray: Mutex<Option<Ray<f64>>>,
+    }
+
+    impl Clone for TestShape {
+        fn clone(&self) -> Self {
+            Self {
+                properties: self.properties.clone(),
+                saved_ray: Mutex::new(*self.saved_ray.lock().unwrap()),
+            }
+        }
+    }
+
+    impl TestShape {
+        fn new() -> Self {
+            Self {
+                properties: ShapeProperties::new(1),
+                saved_ray: Mutex::new(None),
+            }
+        }
+    }
+
+    impl Shape<f64> for TestShape {
+        fn properties(&self) -> &ShapeProperties<f64> {
+            &self.properties
+        }
+
+        fn properties_mut(&mut self) -> &mut ShapeProperties<f64> {
+            &mut self.properties
+        }
+
+        fn local_intersect(&self, ray: Ray<f64>) -> Intersections<'_, f64> {
+            *self.saved_ray.lock().unwrap() = Some(ray);
+            Intersections::new(vec![Intersection::new(1.0, self)])
+        }
+
+        fn local_normal_at(&self, point: Tuple<f64>, _hit: &Intersection<'_, f64>) -> Tuple<f64> {
+            Tuple::new_vector(point.x, point.y, point.z)
+        }
+
+        fn bounds(&self) -> Aabb<f64> {
+            Aabb::new(
+                Tuple::new_point(-1.0, -1.0, -1.0),
+                Tuple::new_point(1.0, 1.0, 1.0),
+            )
+        }
+    }
+
+    #[test]
+    fn default_transformation() {
+        let shape = TestShape::new();
+
+        assert_eq!(*shape.transformation(), Matrix::identity_matrix())
+    }
+
+    #[test]
+    fn assign_transformation() {
+        let mut shape = TestShape::new();
+        let translation = Matrix::<f64, 4>::translation(2.0, 3.0, 4.0);
+        shape.set_transformation(translation.clone()).unwrap();
+
+        assert_eq!(*shape.transformation(), translation);
+        assert_eq!(*shape.inverse(), translation.inverse().unwrap())
+    }
+
+    #[test]
+    fn assign_not_invertible_transformation() {
+        let mut shape = TestShape::new();
+
+        assert!(shape
+            .set_transformation(Matrix::scaling(0.0, 1.0, 1.0))
+            .is_err());
+        assert_eq!(*shape.transformation(), Matrix::identity_matrix())
+    }
+
+    #[test]
+    fn default_material() {
+        let shape = TestShape::new();
+
+        assert_eq!(*shape.material(), Material::default())
+    }
+
+    #[test]
+    fn intersect_scaled_shape() {
+        let mut shape = TestShape::new();
+        shape
+            .set_transformation(Matrix::scaling(2.0, 2.0, 2.0))
+            .unwrap();
+        let ray = Ray::new(
+            Tuple::new_point(0.0, 0.0, -5.0),
+            Tuple::new_vector(0.0, 0.0, 1.0),
+        );
+        shape.intersect(ray);
+        let saved_ray = shape.saved_ray.lock().unwrap().unwrap();
+
+        assert_eq!(saved_ray.origin, Tuple::new_point(0.0, 0.0, -2.5));
+        assert_eq!(saved_ray.direction, Tuple::new_vector(0.0, 0.0, 0.5))
+    }
+
+    #[test]
+    fn intersect_translated_shape() {
+        let mut shape = TestShape::new();
+        shape
+            .set_transformation(Matrix::translation(5.0, 0.0, 0.0))
+            .unwrap();
+        let ray = Ray::new(
+            Tuple::new_point(0.0, 0.0, -5.0),
+            Tuple::new_vector(0.0, 0.0, 1.0),
+        );
+        shape.intersect(ray);
+        let saved_ray = shape.saved_ray.lock().unwrap().unwrap();
+
+        assert_eq!(saved_ray.origin, Tuple::new_point(-5.0, 0.0, -5.0));
+        assert_eq!(saved_ray.direction, Tuple::new_vector(0.0, 0.0, 1.0))
+    }
+
+    #[test]
+    fn normal_on_translated_shape() {
+        let mut shape = TestShape::new();
+        shape
+            .set_transformation(Matrix::translation(0.0, 1.0, 0.0))
+            .unwrap();
+        let normal = shape.normal_at(
+            Tuple::new_point(0.0, 1.0 + FRAC_1_SQRT_2, -FRAC_1_SQRT_2),
+            &Intersection::new(0.0, &shape),
+        );
+
+        assert_eq!(
+            normal,
+            Tuple::new_vector(0.0, FRAC_1_SQRT_2, -FRAC_1_SQRT_2)
+        )
+    }
+
+    #[test]
+    fn normal_on_transformed_shape() {
+        let mut shape = TestShape::new();
+        shape
+            .set_transformation(Matrix::scaling(1.0, 0.5, 1.0) * Matrix::rotate_z(PI / 5.0))
+            .unwrap();
+        let value = 2.0_f64.sqrt() / 2.0;
+        let normal = shape.normal_at(
+            Tuple::new_point(0.0, value, -value),
+            &Intersection::new(0.0, &shape),
+        );
+
+        assert_eq!(normal, Tuple::new_vector(0.0, 0.97014, -0.24254))
+    }
+
+    #[test]
+    fn clone_boxed_shape() {
+        let mut shape = TestShape::new();
+        shape
+            .set_transformation(Matrix::translation(1.0, 0.0, 0.0))
+            .unwrap();
+        let boxed: Box<dyn Shape<f64>> = Box::new(shape);
+        let cloned = boxed.clone();
+
+        assert_eq!(cloned.transformation(), boxed.transformation());
+        assert!(!same_shape(cloned.as_ref(), boxed.as_ref()))
+    }
+
+    #[test]
+    fn bounds_in_parent_space() {
+        let mut shape = TestShape::new();
+        shape
+            .set_transformation(
+                Matrix::translation(1.0, -3.0, 5.0) * Matrix::scaling(0.5, 2.0, 4.0),
+            )
+            .unwrap();
+        let bounds = shape.parent_space_bounds();
+
+        assert_eq!(bounds.min, Tuple::new_point(0.5, -5.0, 1.0));
+        assert_eq!(bounds.max, Tuple::new_point(1.5, -1.0, 9.0))
+    }
+
+    #[test]
+    fn compare_shapes_by_address() {
+        let shape_1 = TestShape::new();
+        let shape_2 = TestShape::new();
+
+        assert!(same_shape(&shape_1, &shape_1));
+        assert!(!same_shape(&shape_1, &shape_2))
+    }
+}
@@ -1,16 +1,18 @@
-use crate::intersection::{Intersection, IntersectionObject, Intersections};
+use crate::bounds::Aabb;
+use crate::intersection::{Intersection, Intersections};
 use crate::ray::Ray;
-use crate::{Matrix, Tuple};
+use crate::shape::{Shape, ShapeProperties};
+use crate::Tuple;
 use num::Float;
 use std::ops::AddAssign;
 
+// unit sphere centered at the origin in object space
 #[derive(Clone, PartialEq, Debug)]
 pub struct Sphere<T>
 where
     T: Float,
 {
-    id: i32,
-    transformation: Matrix<T, 4>,
+    properties: ShapeProperties<T>,
 }
 
 impl<T> Sphere<T>
@@ -20,34 +22,26 @@ where
 {
     pub fn new(id: i32) -> Self {
         Self {
-            id,
-            transformation: Matrix::<T, 4>::identity_matrix(),
+            properties: ShapeProperties::new(id),
         }
     }
-
-    // made it a function for now, maybe
-    // more functionality is added in the future
-    pub fn set_translation(&mut self, transformation: Matrix<T, 4>) {
-        self.transformation = transformation
-    }
 }
 
-// IntersectionObject is used as generic trait
-// for all the objects that intersect with rays
-impl<T> IntersectionObject<T> for Sphere<T>
+impl<T> Shape<T> for Sphere<T>
 where
     T: Float,
     T: AddAssign,
+    T: Send + Sync + 'static,
 {
-    type Object = Sphere<T>;
+    fn properties(&self) -> &ShapeProperties<T> {
+        &self.properties
+    }
 
-    fn intersect(&self, ray: Ray<T>) -> Result<Intersections<T, Self::Object, 2>, &'static str> {
-        let mut inverse = self.transformation.clone();
-        match inverse.inverse() {
-            Ok(inv) => inverse = inv,
-            Err(_) => return Err("inverse failed"),
-        }
-        let ray = ray.transform(inverse);
+    fn properties_mut(&mut self) -> &mut ShapeProperties<T> {
+        &mut self.properties
+    }
+
+    fn local_intersect(&self, ray: Ray<T>) -> Intersections<'_, T> {
         let sphere_to_ray = ray.origin - Tuple::<T>::new_point(T::zero(), T::zero(), T::zero());
         let a = ray.direction.dot_product(&ray.direction);
         let b: T = ray.direction.dot_product(&sphere_to_ray) * T::from(2.0).unwrap();
@@ -55,27 +49,60 @@ where
         let discriminant: T = b.powi(2) - T::from(4.0).unwrap() * a * c;
 
         match discriminant < T::zero() {
-            true => Err("Discriminant is lower then 0"),
+            true => Intersections::empty(),
             false => {
-                let intersection_1 = Intersection::<T, Sphere<T>>::new(
+                let intersection_1 = Intersection::new(
                     (-b - discriminant.sqrt()) / (T::from(2.0).unwrap() * a),
-                    self.clone(),
+                    self,
                 );
-                let intersection_2 = Intersection::<T, Sphere<T>>::new(
+                let intersection_2 = Intersection::new(
                     (-b + discriminant.sqrt()) / (T::from(2.0).unwrap() * a),
-                    self.clone(),
+                    self,
                 );
-                Ok(Intersections::new([intersection_1, intersection_2]))
+                Intersections::new(vec![intersection_1, intersection_2])
             }
         }
     }
+
+    // the normal of a unit sphere is the vector from the origin
+    fn local_normal_at(&self, point: Tuple<T>, _hit: &Intersection<'_, T>) -> Tuple<T> {
+        point - Tuple::<T>::new_point(T::zero(), T::zero(), T::zero())
+    }
+
+    fn bounds(&self) -> Aabb<T> {
+        Aabb::new(
+            Tuple::new_point(-T::one(), -T::one(), -T::one()),
+            Tuple::new_point(T::one(), T::one(), T::one()),
+        )
+    }
 }
 
+#[cfg(test)]
+impl Sphere<f64> {
+    // a sphere of clear glass used by the refraction tests
+    pub(crate) fn glass_sphere(id: i32) -> Self {
+        use crate::material::Material;
+
+        let mut sphere = Self::new(id);
+        sphere.set_material(Material {
+            transparency: 1.0,
+            refractive_index: 1.5,
+            ..Material::default()
+        });
+        sphere
+    }
+}
+
+#[cfg(test)]
 mod tests {
-    use crate::intersection::{Intersection, IntersectionObject};
+    use crate::color::Color;
+    use crate::intersection::Intersection;
+    use crate::material::Material;
     use crate::ray::Ray;
+    use crate::shape::Shape;
     use crate::sphere::Sphere;
     use crate::{Matrix, Tuple};
+    use std::f64::consts::{FRAC_1_SQRT_2, PI};
 
     #[test]
     fn ray_intersection_2_points() {
@@ -85,15 +112,10 @@ mod tests {
         );
         let sphere = Sphere::<f64>::new(1);
 
-        match sphere.intersect(ray) {
-            Ok(inter) => {
-                assert_eq!(inter[0].value, 4.0_f64);
-                assert_eq!(inter[1].value, 6.0_f64)
-            }
-            Err(_) => {
-                assert_eq!(true, false)
-            }
-        }
+        let inter = sphere.intersect(ray);
+
+        assert_eq!(inter[0].value, 4.0_f64);
+        assert_eq!(inter[1].value, 6.0_f64)
     }
 
     #[test]
@@ -104,15 +126,10 @@ mod tests {
         );
         let sphere = Sphere::<f64>::new(1);
 
-        match sphere.intersect(ray) {
-            Ok(inter) => {
-                assert_eq!(inter[0].value, 5.0_f64);
-                assert_eq!(inter[1].value, 5.0_f64)
-            }
-            Err(_) => {
-                assert_eq!(true, false)
-            }
-        }
+        let inter = sphere.intersect(ray);
+
+        assert_eq!(inter[0].value, 5.0_f64);
+        assert_eq!(inter[1].value, 5.0_f64)
     }
 
     #[test]
@@ -123,14 +140,9 @@ mod tests {
         );
         let sphere = Sphere::<f64>::new(1);
 
-        match sphere.intersect(ray) {
-            Ok(_) => {
-                assert_eq!(true, false)
-            }
-            Err(_) => {
-                assert_eq!(true, true)
-            }
-        }
+        let inter = sphere.intersect(ray);
+
+        assert!(inter.is_empty())
     }
 
     #[test]
@@ -141,15 +153,10 @@ mod tests {
         );
         let sphere = Sphere::<f64>::new(1);
 
-        match sphere.intersect(ray) {
-            Ok(inter) => {
-                assert_eq!(inter[0].value, -1.0_f64);
-                assert_eq!(inter[1].value, 1.0_f64)
-            }
-            Err(_) => {
-                assert_eq!(true, false)
-            }
-        }
+        let inter = sphere.intersect(ray);
+
+        assert_eq!(inter[0].value, -1.0_f64);
+        assert_eq!(inter[1].value, 1.0_f64)
     }
 
     #[test]
@@ -160,15 +167,10 @@ mod tests {
         );
         let sphere = Sphere::<f64>::new(1);
 
-        match sphere.intersect(ray) {
-            Ok(inter) => {
-                assert_eq!(inter[0].value, -6.0_f64);
-                assert_eq!(inter[1].value, -4.0_f64)
-            }
-            Err(_) => {
-                assert_eq!(true, false)
-            }
-        }
+        let inter = sphere.intersect(ray);
+
+        assert_eq!(inter[0].value, -6.0_f64);
+        assert_eq!(inter[1].value, -4.0_f64)
     }
 
     #[test]
@@ -176,16 +178,16 @@ mod tests {
         let sphere = Sphere::<f64>::new(1);
         let identity_matrix = Matrix::<f64, 4>::identity_matrix();
 
-        assert_eq!(sphere.transformation, identity_matrix)
+        assert_eq!(*sphere.transformation(), identity_matrix)
     }
 
     #[test]
     fn change_sphere_transformation() {
         let mut sphere = Sphere::<f64>::new(1);
         let translation = Matrix::<f64, 4>::translation(2.0, 3.0, 4.0);
-        sphere.set_translation(translation.clone());
+        sphere.set_transformation(translation.clone()).unwrap();
 
-        assert_eq!(sphere.transformation, translation)
+        assert_eq!(*sphere.transformation(), translation)
     }
 
     #[test]
@@ -195,15 +197,123 @@ mod tests {
             Tuple::<f64>::new_vector(0.0, 0.0, 1.0),
         );
         let mut sphere = Sphere::<f64>::new(1);
-        sphere.set_translation(Matrix::scaling(2.0, 2.0, 2.0));
-        match sphere.intersect(ray) {
-            Ok(inter) => {
-                assert_eq!(inter[0].value, 3.0);
-                assert_eq!(inter[1].value, 7.0)
-            }
-            Err(_) => {
-                assert_eq!(true, false)
-            }
-        }
+        sphere
+            .set_transformation(Matrix::scaling(2.0, 2.0, 2.0))
+            .unwrap();
+        let inter = sphere.intersect(ray);
+
+        assert_eq!(inter[0].value, 3.0);
+        assert_eq!(inter[1].value, 7.0)
+    }
+
+    #[test]
+    fn normal_on_x_axis() {
+        let sphere = Sphere::<f64>::new(1);
+        let normal = sphere.normal_at(
+            Tuple::<f64>::new_point(1.0, 0.0, 0.0),
+            &Intersection::new(0.0, &sphere),
+        );
+
+        assert_eq!(normal, Tuple::<f64>::new_vector(1.0, 0.0, 0.0))
+    }
+
+    #[test]
+    fn normal_on_y_axis() {
+        let sphere = Sphere::<f64>::new(1);
+        let normal = sphere.normal_at(
+            Tuple::<f64>::new_point(0.0, 1.0, 0.0),
+            &Intersection::new(0.0, &sphere),
+        );
+
+        assert_eq!(normal, Tuple::<f64>::new_vector(0.0, 1.0, 0.0))
+    }
+
+    #[test]
+    fn normal_on_z_axis() {
+        let sphere = Sphere::<f64>::new(1);
+        let normal = sphere.normal_at(
+            Tuple::<f64>::new_point(0.0, 0.0, 1.0),
+            &Intersection::new(0.0, &sphere),
+        );
+
+        assert_eq!(normal, Tuple::<f64>::new_vector(0.0, 0.0, 1.0))
+    }
+
+    #[test]
+    fn normal_on_nonaxial_point() {
+        let sphere = Sphere::<f64>::new(1);
+        let value = 3.0_f64.sqrt() / 3.0;
+        let normal = sphere.normal_at(
+            Tuple::<f64>::new_point(value, value, value),
+            &Intersection::new(0.0, &sphere),
+        );
+
+        assert_eq!(normal, Tuple::<f64>::new_vector(value, value, value));
+        assert_eq!(normal, normal.normalize())
+    }
+
+    #[test]
+    fn normal_on_translated_sphere() {
+        let mut sphere = Sphere::<f64>::new(1);
+        sphere
+            .set_transformation(Matrix::<f64, 4>::translation(0.0, 1.0, 0.0))
+            .unwrap();
+        let normal = sphere.normal_at(
+            Tuple::<f64>::new_point(0.0, 1.0 + FRAC_1_SQRT_2, -FRAC_1_SQRT_2),
+            &Intersection::new(0.0, &sphere),
+        );
+
+        assert_eq!(
+            normal,
+            Tuple::<f64>::new_vector(0.0, FRAC_1_SQRT_2, -FRAC_1_SQRT_2)
+        );
+        assert_eq!(normal.w, 0.0)
+    }
+
+    #[test]
+    fn normal_on_transformed_sphere() {
+        let mut sphere = Sphere::<f64>::new(1);
+        sphere
+            .set_transformation(
+                Matrix::<f64, 4>::scaling(1.0, 0.5, 1.0) * Matrix::<f64, 4>::rotate_z(PI / 5.0),
+            )
+            .unwrap();
+        let value = 2.0_f64.sqrt() / 2.0;
+        let normal = sphere.normal_at(
+            Tuple::<f64>::new_point(0.0, value, -value),
+            &Intersection::new(0.0, &sphere),
+        );
+
+        assert_eq!(normal, Tuple::<f64>::new_vector(0.0, 0.97014, -0.24254));
+        assert_eq!(normal.w, 0.0)
+    }
+
+    #[test]
+    fn sphere_default_material() {
+        let sphere = Sphere::<f64>::new(1);
+
+        assert_eq!(*sphere.material(), Material::default())
+    }
+
+    #[test]
+    fn sphere_assigned_material() {
+        let mut sphere = Sphere::<f64>::new(1);
+        let material = Material::<f64> {
+            color: Color::new(1.0, 0.2, 1.0),
+            ambient: 1.0,
+            ..Material::default()
+        };
+        sphere.set_material(material.clone());
+
+        assert_eq!(*sphere.material(), material)
+    }
+
+    #[test]
+    fn glass_sphere_is_transparent() {
+        let sphere = Sphere::glass_sphere(1);
+
+        assert_eq!(*sphere.transformation(), Matrix::identity_matrix());
+        assert_eq!(sphere.material().transparency, 1.0);
+        assert_eq!(sphere.material().refractive_index, 1.5)
     }
 }
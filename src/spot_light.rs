@@ -0,0 +1,211 @@
+use crate::color::Color;
+use crate::light::{sample_towards, Light, LightSample};
+use crate::tuple::Tuple;
+use num::Float;
+
+/*
+    This file contains the spot light. It is a point light that only
+    shines inside of a cone. Inside of the inner angle the light is at
+    full strength and it fades out smoothly towards the outer angle
+*/
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpotLight<T>
+where
+    T: Float,
+{
+    pub position: Tuple<T>,
+    // the direction the cone points in, it does not have to be normalized
+    pub direction: Tuple<T>,
+    // the angles in radians between the direction and the edge of the
+    // full strength cone and the edge of the light
+    pub inner_angle: T,
+    pub outer_angle: T,
+    pub intensity: Color,
+}
+
+impl<T> SpotLight<T>
+where
+    T: Float,
+{
+    // create a spot light, an inner angle that is larger than
+    // the outer angle is made equal to the outer angle
+    pub fn new(
+        position: Tuple<T>,
+        direction: Tuple<T>,
+        inner_angle: T,
+        outer_angle: T,
+        intensity: Color,
+    ) -> Self {
+        Self {
+            position,
+            direction,
+            inner_angle: inner_angle.min(outer_angle),
+            outer_angle,
+            intensity,
+        }
+    }
+
+    // return how much of the light reaches the point, 1 inside of the
+    // inner cone and 0 outside of the outer cone. in between the light
+    // fades with a smoothstep so the edge of the cone is not visible
+    pub fn falloff(&self, point: Tuple<T>) -> T {
+        let cos_angle = (point - self.position)
+            .normalize()
+            .dot_product(&self.direction.normalize());
+        let (cos_inner, cos_outer) = (self.inner_angle.cos(), self.outer_angle.cos());
+        if cos_angle >= cos_inner {
+            return T::one();
+        }
+        if cos_angle <= cos_outer {
+            return T::zero();
+        }
+        let t = (cos_angle - cos_outer) / (cos_inner - cos_outer);
+        t * t * (T::from(3.0).unwrap() - T::from(2.0).unwrap() * t)
+    }
+}
+
+impl<T> Light<T> for SpotLight<T>
+where
+    T: Float,
+    T: Send + Sync,
+{
+    fn intensity(&self) -> &Color {
+        &self.intensity
+    }
+
+    // the ambient light fades with the falloff as well, so
+    // the light does not spill outside of its cone
+    fn ambient_intensity(&self, point: Tuple<T>) -> Color {
+        self.intensity.clone() * self.falloff(point).to_f64().unwrap()
+    }
+
+    fn samples(&self, point: Tuple<T>) -> Vec<LightSample<T>> {
+        let intensity = self.intensity.clone() * self.falloff(point).to_f64().unwrap();
+        vec![sample_towards(self.position, point, intensity)]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::color::Color;
+    use crate::light::{lighting, Light};
+    use crate::material::Material;
+    use crate::sphere::Sphere;
+    use crate::spot_light::SpotLight;
+    use crate::tuple::Tuple;
+    use std::f64::consts::{FRAC_PI_4, FRAC_PI_6};
+
+    // a spot light above the origin that points down
+    fn spot_light() -> SpotLight<f64> {
+        SpotLight::new(
+            Tuple::new_point(0.0, 10.0, 0.0),
+            Tuple::new_vector(0.0, -2.0, 0.0),
+            FRAC_PI_6,
+            FRAC_PI_4,
+            Color::new(1.0, 1.0, 1.0),
+        )
+    }
+
+    #[test]
+    fn create_spot_light() {
+        let light = spot_light();
+
+        assert_eq!(light.position, Tuple::new_point(0.0, 10.0, 0.0));
+        assert_eq!(light.direction, Tuple::new_vector(0.0, -2.0, 0.0));
+        assert_eq!(light.inner_angle, FRAC_PI_6);
+        assert_eq!(light.outer_angle, FRAC_PI_4);
+        assert_eq!(light.intensity, Color::new(1.0, 1.0, 1.0))
+    }
+
+    #[test]
+    fn inner_angle_is_not_larger_than_outer_angle() {
+        let light = SpotLight::new(
+            Tuple::new_point(0.0, 10.0, 0.0),
+            Tuple::new_vector(0.0, -1.0, 0.0),
+            FRAC_PI_4,
+            FRAC_PI_6,
+            Color::new(1.0, 1.0, 1.0),
+        );
+
+        assert_eq!(light.inner_angle, FRAC_PI_6)
+    }
+
+    #[test]
+    fn falloff_of_spot_light() {
+        let light = spot_light();
+        // tan(pi / 6) * 10 and tan(pi / 4) * 10 are the edges of the cones
+        let parameters = [
+            ((0.0, 0.0, 0.0), 1.0),
+            ((5.0, 0.0, 0.0), 1.0),
+            ((0.0, 0.0, -5.7), 1.0),
+            ((10.5, 0.0, 0.0), 0.0),
+            ((0.0, 20.0, 0.0), 0.0),
+            ((-20.0, 0.0, 0.0), 0.0),
+        ];
+
+        for ((x, y, z), falloff) in parameters {
+            assert_eq!(light.falloff(Tuple::new_point(x, y, z)), falloff)
+        }
+    }
+
+    #[test]
+    fn falloff_fades_between_cones() {
+        let light = spot_light();
+        let mut previous = 1.0;
+        for i in 0..10 {
+            let x = 5.8 + i as f64 * 0.4;
+            let falloff = light.falloff(Tuple::new_point(x, 0.0, 0.0));
+
+            assert!(falloff > 0.0 && falloff < 1.0);
+            assert!(falloff < previous);
+            previous = falloff
+        }
+    }
+
+    #[test]
+    fn spot_light_sample_is_weakened_by_falloff() {
+        let light = spot_light();
+        let point = Tuple::new_point(8.0, 0.0, 0.0);
+        let samples = light.samples(point);
+        let falloff = light.falloff(point);
+
+        assert_eq!(samples.len(), 1);
+        assert_eq!(samples[0].direction, (light.position - point).normalize());
+        assert_eq!(samples[0].intensity, Color::new(falloff, falloff, falloff));
+        assert_eq!(
+            light.ambient_intensity(point),
+            Color::new(falloff, falloff, falloff)
+        )
+    }
+
+    #[test]
+    fn lighting_outside_of_cone_is_black() {
+        let material = Material::<f64>::default();
+        let eyev = Tuple::new_vector(0.0, 1.0, 0.0);
+        let normalv = Tuple::new_vector(0.0, 1.0, 0.0);
+        let light = spot_light();
+        let sphere = Sphere::new(1);
+
+        let inside = lighting(
+            &material,
+            &sphere,
+            &light,
+            Tuple::new_point(0.0, 0.0, 0.0),
+            eyev,
+            normalv,
+            1.0,
+        );
+        let outside = lighting(
+            &material,
+            &sphere,
+            &light,
+            Tuple::new_point(20.0, 0.0, 0.0),
+            eyev,
+            normalv,
+            1.0,
+        );
+        assert_eq!(inside, Color::new(1.9, 1.9, 1.9));
+        assert_eq!(outside, Color::new(0.0, 0.0, 0.0))
+    }
+}
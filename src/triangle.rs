@@ -0,0 +1,452 @@
+use crate::bounds::Aabb;
+use crate::float_service::EPSILON;
+use crate::intersection::{Intersection, Intersections};
+use crate::ray::Ray;
+use crate::shape::{Shape, ShapeProperties};
+use crate::Tuple;
+use num::Float;
+use std::ops::AddAssign;
+
+/*
+    This file contains the triangle shapes. A triangle has a single flat
+    normal, a smooth triangle interpolates the normals of its corners
+    with the barycentric coordinates of the hit
+*/
+
+// intersect the ray with the triangle spanned by p1 and the edges e1 and
+// e2 with the Möller–Trumbore algorithm. returns the value of the hit and
+// the barycentric u and v coordinates when the ray hits the triangle
+fn intersect_triangle<T>(
+    ray: &Ray<T>,
+    p1: Tuple<T>,
+    e1: Tuple<T>,
+    e2: Tuple<T>,
+) -> Option<(T, T, T)>
+where
+    T: Float,
+{
+    let dir_cross_e2 = ray.direction.cross_product(&e2);
+    let determinant = e1.dot_product(&dir_cross_e2);
+
+    // the ray is parallel to the plane of the triangle. the determinant
+    // grows with the size of the edges and the direction, so the tolerance
+    // is scaled by them and small triangles can still be hit
+    let scale = e1.magnitude() * e2.magnitude() * ray.direction.magnitude();
+    if determinant.abs() <= T::from(EPSILON).unwrap() * scale {
+        return None;
+    }
+
+    let f = T::one() / determinant;
+    let p1_to_origin = ray.origin - p1;
+    let u = f * p1_to_origin.dot_product(&dir_cross_e2);
+    if u < T::zero() || u > T::one() {
+        return None;
+    }
+
+    let origin_cross_e1 = p1_to_origin.cross_product(&e1);
+    let v = f * ray.direction.dot_product(&origin_cross_e1);
+    if v < T::zero() || u + v > T::one() {
+        return None;
+    }
+
+    Some((f * e2.dot_product(&origin_cross_e1), u, v))
+}
+
+// a triangle whose edges point in the same direction has no area
+fn check_degenerate<T>(e1: Tuple<T>, e2: Tuple<T>) -> Result<(), &'static str>
+where
+    T: Float,
+{
+    match e1.cross_product(&e2).magnitude() == T::zero() {
+        true => Err("triangle is degenerate"),
+        false => Ok(()),
+    }
+}
+
+// flat triangle, the edges and the normal are computed once
+// when the triangle is created
+#[derive(Clone, PartialEq, Debug)]
+pub struct Triangle<T>
+where
+    T: Float,
+{
+    properties: ShapeProperties<T>,
+    p1: Tuple<T>,
+    p2: Tuple<T>,
+    p3: Tuple<T>,
+    e1: Tuple<T>,
+    e2: Tuple<T>,
+    normal: Tuple<T>,
+}
+
+impl<T> Triangle<T>
+where
+    T: Float,
+    T: AddAssign,
+{
+    // create a triangle, fails when the corners lie on a single
+    // line because such a triangle has no normal
+    pub fn new(id: i32, p1: Tuple<T>, p2: Tuple<T>, p3: Tuple<T>) -> Result<Self, &'static str> {
+        let e1 = p2 - p1;
+        let e2 = p3 - p1;
+        check_degenerate(e1, e2)?;
+
+        Ok(Self {
+            properties: ShapeProperties::new(id),
+            p1,
+            p2,
+            p3,
+            e1,
+            e2,
+            normal: e2.cross_product(&e1).normalize(),
+        })
+    }
+
+    pub fn p1(&self) -> Tuple<T> {
+        self.p1
+    }
+
+    pub fn p2(&self) -> Tuple<T> {
+        self.p2
+    }
+
+    pub fn p3(&self) -> Tuple<T> {
+        self.p3
+    }
+
+    pub fn e1(&self) -> Tuple<T> {
+        self.e1
+    }
+
+    pub fn e2(&self) -> Tuple<T> {
+        self.e2
+    }
+
+    pub fn normal(&self) -> Tuple<T> {
+        self.normal
+    }
+}
+
+impl<T> Shape<T> for Triangle<T>
+where
+    T: Float,
+    T: AddAssign,
+    T: Send + Sync + 'static,
+{
+    fn properties(&self) -> &ShapeProperties<T> {
+        &self.properties
+    }
+
+    fn properties_mut(&mut self) -> &mut ShapeProperties<T> {
+        &mut self.properties
+    }
+
+    fn local_intersect(&self, ray: Ray<T>) -> Intersections<'_, T> {
+        match intersect_triangle(&ray, self.p1, self.e1, self.e2) {
+            None => Intersections::empty(),
+            Some((value, _, _)) => Intersections::new(vec![Intersection::new(value, self)]),
+        }
+    }
+
+    fn local_normal_at(&self, _point: Tuple<T>, _hit: &Intersection<'_, T>) -> Tuple<T> {
+        self.normal
+    }
+
+    fn bounds(&self) -> Aabb<T> {
+        let mut bounds = Aabb::empty();
+        [self.p1, self.p2, self.p3]
+            .into_iter()
+            .for_each(|point| bounds.add_point(point));
+        bounds
+    }
+}
+
+// triangle with a normal on every corner, the normal at a hit is
+// interpolated between them so meshes of triangles look smooth
+#[derive(Clone, PartialEq, Debug)]
+pub struct SmoothTriangle<T>
+where
+    T: Float,
+{
+    properties: ShapeProperties<T>,
+    p1: Tuple<T>,
+    p2: Tuple<T>,
+    p3: Tuple<T>,
+    n1: Tuple<T>,
+    n2: Tuple<T>,
+    n3: Tuple<T>,
+    e1: Tuple<T>,
+    e2: Tuple<T>,
+}
+
+impl<T> SmoothTriangle<T>
+where
+    T: Float,
+    T: AddAssign,
+{
+    pub fn new(
+        id: i32,
+        (p1, p2, p3): (Tuple<T>, Tuple<T>, Tuple<T>),
+        (n1, n2, n3): (Tuple<T>, Tuple<T>, Tuple<T>),
+    ) -> Result<Self, &'static str> {
+        check_degenerate(p2 - p1, p3 - p1)?;
+
+        Ok(Self {
+            properties: ShapeProperties::new(id),
+            p1,
+            p2,
+            p3,
+            n1,
+            n2,
+            n3,
+            e1: p2 - p1,
+            e2: p3 - p1,
+        })
+    }
+
+    pub fn p1(&self) -> Tuple<T> {
+        self.p1
+    }
+
+    pub fn p2(&self) -> Tuple<T> {
+        self.p2
+    }
+
+    pub fn p3(&self) -> Tuple<T> {
+        self.p3
+    }
+
+    pub fn n1(&self) -> Tuple<T> {
+        self.n1
+    }
+
+    pub fn n2(&self) -> Tuple<T> {
+        self.n2
+    }
+
+    pub fn n3(&self) -> Tuple<T> {
+        self.n3
+    }
+}
+
+impl<T> Shape<T> for SmoothTriangle<T>
+where
+    T: Float,
+    T: AddAssign,
+    T: Send + Sync + 'static,
+{
+    fn properties(&self) -> &ShapeProperties<T> {
+        &self.properties
+    }
+
+    fn properties_mut(&mut self) -> &mut ShapeProperties<T> {
+        &mut self.properties
+    }
+
+    // the intersection keeps u and v so the normal can be interpolated
+    fn local_intersect(&self, ray: Ray<T>) -> Intersections<'_, T> {
+        match intersect_triangle(&ray, self.p1, self.e1, self.e2) {
+            None => Intersections::empty(),
+            Some((value, u, v)) => {
+                Intersections::new(vec![Intersection::new_with_uv(value, self, u, v)])
+            }
+        }
+    }
+
+    fn local_normal_at(&self, _point: Tuple<T>, hit: &Intersection<'_, T>) -> Tuple<T> {
+        self.n2 * hit.u + self.n3 * hit.v + self.n1 * (T::one() - hit.u - hit.v)
+    }
+
+    fn bounds(&self) -> Aabb<T> {
+        let mut bounds = Aabb::empty();
+        [self.p1, self.p2, self.p3]
+            .into_iter()
+            .for_each(|point| bounds.add_point(point));
+        bounds
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::intersection::{prepare_computations, Intersection, Intersections};
+    use crate::ray::Ray;
+    use crate::shape::Shape;
+    use crate::triangle::{SmoothTriangle, Triangle};
+    use crate::Tuple;
+
+    fn test_triangle() -> Triangle<f64> {
+        Triangle::new(
+            1,
+            Tuple::new_point(0.0, 1.0, 0.0),
+            Tuple::new_point(-1.0, 0.0, 0.0),
+            Tuple::new_point(1.0, 0.0, 0.0),
+        )
+        .unwrap()
+    }
+
+    fn test_smooth_triangle() -> SmoothTriangle<f64> {
+        SmoothTriangle::new(
+            1,
+            (
+                Tuple::new_point(0.0, 1.0, 0.0),
+                Tuple::new_point(-1.0, 0.0, 0.0),
+                Tuple::new_point(1.0, 0.0, 0.0),
+            ),
+            (
+                Tuple::new_vector(0.0, 1.0, 0.0),
+                Tuple::new_vector(-1.0, 0.0, 0.0),
+                Tuple::new_vector(1.0, 0.0, 0.0),
+            ),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn create_triangle() {
+        let triangle = test_triangle();
+
+        assert_eq!(triangle.p1(), Tuple::new_point(0.0, 1.0, 0.0));
+        assert_eq!(triangle.p2(), Tuple::new_point(-1.0, 0.0, 0.0));
+        assert_eq!(triangle.p3(), Tuple::new_point(1.0, 0.0, 0.0));
+        assert_eq!(triangle.e1(), Tuple::new_vector(-1.0, -1.0, 0.0));
+        assert_eq!(triangle.e2(), Tuple::new_vector(1.0, -1.0, 0.0));
+        assert_eq!(triangle.normal(), Tuple::new_vector(0.0, 0.0, -1.0))
+    }
+
+    #[test]
+    fn normal_on_triangle() {
+        let triangle = test_triangle();
+        let hit = Intersection::new(0.0, &triangle);
+
+        for (x, y) in [(0.0, 0.5), (-0.5, 0.75), (0.5, 0.25)] {
+            let normal = triangle.local_normal_at(Tuple::new_point(x, y, 0.0), &hit);
+
+            assert_eq!(normal, triangle.normal())
+        }
+    }
+
+    #[test]
+    fn ray_parallel_to_triangle() {
+        let triangle = test_triangle();
+        let ray = Ray::new(
+            Tuple::new_point(0.0, -1.0, -2.0),
+            Tuple::new_vector(0.0, 1.0, 0.0),
+        );
+
+        assert!(triangle.local_intersect(ray).is_empty())
+    }
+
+    #[test]
+    fn ray_misses_triangle_edges() {
+        let triangle = test_triangle();
+        let origins = [(1.0, 1.0, -2.0), (-1.0, 1.0, -2.0), (0.0, -1.0, -2.0)];
+
+        for (x, y, z) in origins {
+            let ray = Ray::new(Tuple::new_point(x, y, z), Tuple::new_vector(0.0, 0.0, 1.0));
+
+            assert!(triangle.local_intersect(ray).is_empty())
+        }
+    }
+
+    #[test]
+    fn ray_hits_triangle() {
+        let triangle = test_triangle();
+        let ray = Ray::new(
+            Tuple::new_point(0.0, 0.5, -2.0),
+            Tuple::new_vector(0.0, 0.0, 1.0),
+        );
+        let inter = triangle.local_intersect(ray);
+
+        assert_eq!(inter.len(), 1);
+        assert_eq!(inter[0].value, 2.0)
+    }
+
+    #[test]
+    fn intersection_with_smooth_triangle_stores_uv() {
+        let triangle = test_smooth_triangle();
+        let ray = Ray::new(
+            Tuple::new_point(-0.2, 0.3, -2.0),
+            Tuple::new_vector(0.0, 0.0, 1.0),
+        );
+        let inter = triangle.local_intersect(ray);
+
+        assert!((inter[0].u - 0.45).abs() < 0.0001);
+        assert!((inter[0].v - 0.25).abs() < 0.0001)
+    }
+
+    #[test]
+    fn smooth_triangle_interpolates_normal() {
+        let triangle = test_smooth_triangle();
+        let hit = Intersection::new_with_uv(1.0, &triangle, 0.45, 0.25);
+        let normal = triangle.normal_at(Tuple::new_point(0.0, 0.0, 0.0), &hit);
+
+        assert_eq!(normal, Tuple::new_vector(-0.5547, 0.83205, 0.0))
+    }
+
+    #[test]
+    fn prepare_normal_on_smooth_triangle() {
+        let triangle = test_smooth_triangle();
+        let hit = Intersection::new_with_uv(1.0, &triangle, 0.45, 0.25);
+        let ray = Ray::new(
+            Tuple::new_point(-0.2, 0.3, -2.0),
+            Tuple::new_vector(0.0, 0.0, 1.0),
+        );
+        let comps = prepare_computations(&hit, ray, &Intersections::new(vec![hit]));
+
+        assert_eq!(comps.normalv, Tuple::new_vector(-0.5547, 0.83205, 0.0))
+    }
+
+    #[test]
+    fn bounds_of_triangle() {
+        let triangle = Triangle::<f64>::new(
+            1,
+            Tuple::new_point(-3.0, 7.0, 2.0),
+            Tuple::new_point(6.0, 2.0, -4.0),
+            Tuple::new_point(2.0, -1.0, -1.0),
+        )
+        .unwrap();
+        let bounds = triangle.bounds();
+
+        assert_eq!(bounds.min, Tuple::new_point(-3.0, -1.0, -4.0));
+        assert_eq!(bounds.max, Tuple::new_point(6.0, 7.0, 2.0))
+    }
+
+    #[test]
+    fn ray_hits_small_triangle() {
+        let triangle = Triangle::<f64>::new(
+            1,
+            Tuple::new_point(0.0, 0.002, 0.0),
+            Tuple::new_point(-0.002, 0.0, 0.0),
+            Tuple::new_point(0.002, 0.0, 0.0),
+        )
+        .unwrap();
+        let ray = Ray::new(
+            Tuple::new_point(0.0, 0.001, -2.0),
+            Tuple::new_vector(0.0, 0.0, 1.0),
+        );
+        let inter = triangle.local_intersect(ray);
+
+        assert_eq!(inter.len(), 1);
+        assert_eq!(inter[0].value, 2.0)
+    }
+
+    #[test]
+    fn degenerate_triangle_is_rejected() {
+        let parameters = [
+            ((0.0, 0.0, 0.0), (1.0, 0.0, 0.0), (2.0, 0.0, 0.0)),
+            ((1.0, 1.0, 1.0), (1.0, 1.0, 1.0), (0.0, 1.0, 0.0)),
+        ];
+
+        for ((x1, y1, z1), (x2, y2, z2), (x3, y3, z3)) in parameters {
+            let (p1, p2, p3) = (
+                Tuple::<f64>::new_point(x1, y1, z1),
+                Tuple::new_point(x2, y2, z2),
+                Tuple::new_point(x3, y3, z3),
+            );
+            let normal = Tuple::new_vector(0.0, 1.0, 0.0);
+
+            assert!(Triangle::new(1, p1, p2, p3).is_err());
+            assert!(SmoothTriangle::new(1, (p1, p2, p3), (normal, normal, normal)).is_err())
+        }
+    }
+}
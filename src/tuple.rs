@@ -77,6 +77,11 @@ where
             self.x * t2.y - self.y * t2.x,
         )
     }
+
+    // reflect the vector around the normal, like a ball bouncing off a wall
+    pub fn reflect(&self, normal: &Tuple<T>) -> Tuple<T> {
+        *self - *normal * (T::from(2.0).unwrap() * self.dot_product(normal))
+    }
 }
 
 impl<T> Display for Tuple<T>
@@ -128,13 +133,15 @@ impl<T> Neg for Tuple<T>
 where
     T: Float,
 {
-    type Output = ();
+    type Output = Tuple<T>;
 
-    fn neg(mut self) -> Self::Output {
-        self.x = self.x.neg();
-        self.y = self.y.neg();
-        self.z = self.z.neg();
-        self.w = self.w.neg();
+    fn neg(self) -> Self::Output {
+        Tuple {
+            x: self.x.neg(),
+            y: self.y.neg(),
+            z: self.z.neg(),
+            w: self.w.neg(),
+        }
     }
 }
 
@@ -305,6 +312,17 @@ mod tests {
         assert_eq!(-point.w, 4.0);
     }
 
+    #[test]
+    fn negate_whole_tuple() {
+        let point = Tuple::new(1.0, -2.0, 3.0, -4.0);
+        let negated = -point;
+
+        assert_eq!(negated.x, -1.0);
+        assert_eq!(negated.y, 2.0);
+        assert_eq!(negated.z, -3.0);
+        assert_eq!(negated.w, 4.0);
+    }
+
     #[test]
     fn multiply_tuple() {
         let point1 = Tuple::new(1.0, -2.0, 3.0, -4.0);
@@ -385,4 +403,21 @@ mod tests {
         assert_eq!(Tuple::cross_product(&vector1, &vector2), right_vector1);
         assert_eq!(Tuple::cross_product(&vector2, &vector1), right_vector2);
     }
+
+    #[test]
+    fn reflect_vector_at_45_degrees() {
+        let vector = Tuple::new_vector(1.0, -1.0, 0.0);
+        let normal = Tuple::new_vector(0.0, 1.0, 0.0);
+
+        assert_eq!(vector.reflect(&normal), Tuple::new_vector(1.0, 1.0, 0.0))
+    }
+
+    #[test]
+    fn reflect_vector_off_slanted_surface() {
+        let value = std::f64::consts::FRAC_1_SQRT_2;
+        let vector = Tuple::new_vector(0.0, -1.0, 0.0);
+        let normal = Tuple::new_vector(value, value, 0.0);
+
+        assert_eq!(vector.reflect(&normal), Tuple::new_vector(1.0, 0.0, 0.0))
+    }
 }
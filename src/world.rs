@@ -0,0 +1,949 @@
+use crate::bvh::{Bvh, BvhStats};
+use crate::color::Color;
+use crate::intersection::{prepare_computations, schlick, Computations, Intersections};
+use crate::light::{lighting, Light, LightSample};
+use crate::ray::Ray;
+use crate::shape::Shape;
+use crate::tuple::Tuple;
+use num::Float;
+use std::ops::AddAssign;
+use std::sync::OnceLock;
+
+/*
+    This file contains the world struct. The world holds every object
+    and light of a scene and is used to find the color seen by a ray
+*/
+
+// how the world finds the objects that are hit by a ray
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Acceleration {
+    // every object is tested by every ray
+    None,
+    // the objects are sorted into a bounding volume hierarchy
+    Bvh,
+}
+
+pub struct World<T>
+where
+    T: Float,
+{
+    objects: Vec<Box<dyn Shape<T>>>,
+    pub lights: Vec<Box<dyn Light<T>>>,
+    pub background: Color,
+    // how many times a ray may bounce off reflective surfaces, this
+    // keeps two mirrors that face each other from recursing forever
+    pub max_depth: usize,
+    acceleration: Acceleration,
+    // built by the first ray that needs it and thrown away
+    // whenever the objects can change
+    bvh: OnceLock<Bvh<T>>,
+}
+
+// the default world is empty and has a black background
+impl<T> Default for World<T>
+where
+    T: Float,
+    T: AddAssign,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> World<T>
+where
+    T: Float,
+    T: AddAssign,
+{
+    // create an empty world with a black background
+    pub fn new() -> Self {
+        Self::with_acceleration(Acceleration::None)
+    }
+
+    // create an empty world that uses the given acceleration
+    // structure to intersect rays with its objects
+    pub fn with_acceleration(acceleration: Acceleration) -> Self {
+        Self {
+            objects: vec![],
+            lights: vec![],
+            background: Color::new(0.0, 0.0, 0.0),
+            max_depth: 5,
+            acceleration,
+            bvh: OnceLock::new(),
+        }
+    }
+
+    pub fn acceleration(&self) -> Acceleration {
+        self.acceleration
+    }
+
+    // adding an object throws away the bvh, it is built
+    // again when the next ray is cast
+    pub fn add_object(&mut self, object: Box<dyn Shape<T>>) {
+        self.objects.push(object);
+        self.bvh = OnceLock::new()
+    }
+
+    pub fn objects(&self) -> &[Box<dyn Shape<T>>] {
+        &self.objects
+    }
+
+    // give access to the objects so they can be changed, removed or
+    // reordered. the bvh is thrown away because it may no longer fit
+    pub fn objects_mut(&mut self) -> &mut Vec<Box<dyn Shape<T>>> {
+        self.bvh = OnceLock::new();
+        &mut self.objects
+    }
+
+    // return the bvh of the world, None when the world does not use one
+    fn bvh(&self) -> Option<&Bvh<T>> {
+        match self.acceleration {
+            Acceleration::None => None,
+            Acceleration::Bvh => Some(self.bvh.get_or_init(|| Bvh::build(&self.objects))),
+        }
+    }
+
+    // return the size of the bvh, None when the world does not use one
+    pub fn bvh_stats(&self) -> Option<BvhStats> {
+        self.bvh().map(|bvh| bvh.stats())
+    }
+
+    pub fn add_light(&mut self, light: Box<dyn Light<T>>) {
+        self.lights.push(light)
+    }
+
+    // intersect the ray with every object in the world and return all
+    // the intersections sorted by their value. with a bvh the objects that
+    // are completely beyond the hit are skipped and not in the result
+    pub fn intersect_world(&self, ray: Ray<T>) -> Intersections<'_, T> {
+        if let Some(bvh) = self.bvh() {
+            return bvh.intersect(&self.objects, ray);
+        }
+        let mut intersections = Intersections::empty();
+        self.objects
+            .iter()
+            .for_each(|object| intersections.merge(object.intersect(ray)));
+        intersections
+    }
+
+    // return true when any object is hit by the ray before the given
+    // distance. it stops at the first hit found, also inside of groups,
+    // and does not sort the intersections, which is all a shadow ray needs
+    pub fn any_hit(&self, ray: Ray<T>, distance: T) -> bool {
+        if let Some(bvh) = self.bvh() {
+            return bvh.any_hit(&self.objects, ray, distance);
+        }
+        self.objects
+            .iter()
+            .any(|object| object.any_hit(ray, distance))
+    }
+
+    // check if the sample of a light is hidden from the point by casting a
+    // ray from the point to the light and looking for an object between
+    fn sample_is_shadowed(&self, sample: &LightSample<T>, point: Tuple<T>) -> bool {
+        self.any_hit(Ray::new(point, sample.direction), sample.distance)
+    }
+
+    // return the part of the light that is visible from the point, 0 when
+    // every sample of the light is hidden and 1 when none of them are
+    pub fn intensity_at(&self, light: &dyn Light<T>, point: Tuple<T>) -> T {
+        let samples = light.samples(point);
+        let visible = samples
+            .iter()
+            .filter(|sample| !self.sample_is_shadowed(sample, point))
+            .count();
+        T::from(visible).unwrap() / T::from(samples.len()).unwrap()
+    }
+
+    // check if the point is completely in the shadow of the light
+    pub fn is_shadowed(&self, light: &dyn Light<T>, point: Tuple<T>) -> bool {
+        self.intensity_at(light, point) == T::zero()
+    }
+
+    // return the color seen by the ray, when nothing is
+    // hit the background color is returned
+    pub fn color_at(&self, ray: Ray<T>) -> Color {
+        self.color_at_depth(ray, self.max_depth)
+    }
+
+    // return the color seen by the ray, the ray may still
+    // bounce off remaining reflective surfaces
+    pub fn color_at_depth(&self, ray: Ray<T>, remaining: usize) -> Color {
+        let intersections = self.intersect_world(ray);
+        match intersections.hit() {
+            None => self.background.clone(),
+            Some(hit) => {
+                self.shade_hit(&prepare_computations(&hit, ray, &intersections), remaining)
+            }
+        }
+    }
+
+    // shade the hit by adding the light of every light source in
+    // the world and the color that is reflected by the surface
+    pub fn shade_hit(&self, comps: &Computations<'_, T>, remaining: usize) -> Color {
+        let surface = self
+            .lights
+            .iter()
+            .fold(Color::new(0.0, 0.0, 0.0), |color, light| {
+                let visible = self.intensity_at(light.as_ref(), comps.over_point);
+                color
+                    + lighting(
+                        comps.object.material(),
+                        comps.object,
+                        light.as_ref(),
+                        comps.over_point,
+                        comps.eyev,
+                        comps.normalv,
+                        visible,
+                    )
+            });
+        let reflected = self.reflected_color(comps, remaining);
+        let refracted = self.refracted_color(comps, remaining);
+
+        // a surface that both reflects and refracts shows more of the
+        // reflection when it is seen at a steep angle
+        let material = comps.object.material();
+        if material.reflective > T::zero() && material.transparency > T::zero() {
+            let reflectance = schlick(comps).to_f64().unwrap();
+            return surface + reflected * reflectance + refracted * (1.0 - reflectance);
+        }
+        surface + reflected + refracted
+    }
+
+    // cast a ray along the reflection vector and return the color it
+    // sees, weakened by how reflective the surface is. black is returned
+    // for surfaces that do not reflect and when no bounces remain
+    pub fn reflected_color(&self, comps: &Computations<'_, T>, remaining: usize) -> Color {
+        let reflective = comps.object.material().reflective;
+        if remaining == 0 || reflective == T::zero() {
+            return Color::new(0.0, 0.0, 0.0);
+        }
+        let ray = Ray::new(comps.over_point, comps.reflectv);
+        self.color_at_depth(ray, remaining - 1) * reflective.to_f64().unwrap()
+    }
+
+    // cast a ray through the surface that is bent by snell's law and return
+    // the color it sees, weakened by how transparent the surface is. black is
+    // returned for opaque surfaces, when no bounces remain and when all the
+    // light is reflected by total internal reflection
+    pub fn refracted_color(&self, comps: &Computations<'_, T>, remaining: usize) -> Color {
+        let transparency = comps.object.material().transparency;
+        if remaining == 0 || transparency == T::zero() {
+            return Color::new(0.0, 0.0, 0.0);
+        }
+        let ratio = comps.n1 / comps.n2;
+        let cos_i = comps.eyev.dot_product(&comps.normalv);
+        let sin2_t = ratio.powi(2) * (T::one() - cos_i.powi(2));
+        if sin2_t > T::one() {
+            return Color::new(0.0, 0.0, 0.0);
+        }
+        let cos_t = (T::one() - sin2_t).sqrt();
+        let direction = comps.normalv * (ratio * cos_i - cos_t) - comps.eyev * ratio;
+        let ray = Ray::new(comps.under_point, direction);
+        self.color_at_depth(ray, remaining - 1) * transparency.to_f64().unwrap()
+    }
+}
+
+#[cfg(test)]
+impl World<f64> {
+    // the default world used throughout the tests, two concentric
+    // spheres lit by a single white point light
+    pub(crate) fn test_world() -> Self {
+        use crate::light::PointLight;
+        use crate::material::Material;
+        use crate::sphere::Sphere;
+        use crate::Matrix;
+
+        let mut world = Self::new();
+        let mut sphere_1 = Sphere::new(1);
+        sphere_1.set_material(Material {
+            color: Color::new(0.8, 1.0, 0.6),
+            diffuse: 0.7,
+            specular: 0.2,
+            ..Material::default()
+        });
+        let mut sphere_2 = Sphere::new(2);
+        sphere_2
+            .set_transformation(Matrix::scaling(0.5, 0.5, 0.5))
+            .unwrap();
+
+        world.add_object(Box::new(sphere_1));
+        world.add_object(Box::new(sphere_2));
+        world.add_light(Box::new(PointLight::new(
+            Tuple::new_point(-10.0, 10.0, -10.0),
+            Color::new(1.0, 1.0, 1.0),
+        )));
+        world
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::area_light::AreaLight;
+    use crate::color::Color;
+    use crate::directional_light::DirectionalLight;
+    use crate::intersection::{prepare_computations, Intersection, Intersections};
+    use crate::light::PointLight;
+    use crate::material::Material;
+    use crate::pattern::test_pattern;
+    use crate::plane::Plane;
+    use crate::ray::Ray;
+    use crate::shape::Shape;
+    use crate::sphere::Sphere;
+    use crate::spot_light::SpotLight;
+    use crate::world::{Acceleration, World};
+    use crate::{Matrix, Tuple};
+
+    #[test]
+    fn create_empty_world() {
+        let world = World::<f64>::new();
+
+        assert_eq!(world.objects.len(), 0);
+        assert_eq!(world.lights.len(), 0);
+        assert_eq!(world.background, Color::new(0.0, 0.0, 0.0));
+        assert_eq!(world.max_depth, 5)
+    }
+
+    #[test]
+    fn create_world_with_bvh() {
+        let world = World::<f64>::with_acceleration(Acceleration::Bvh);
+
+        assert_eq!(World::<f64>::new().acceleration(), Acceleration::None);
+        assert_eq!(world.acceleration(), Acceleration::Bvh);
+        assert!(World::<f64>::new().bvh_stats().is_none());
+        assert_eq!(world.bvh_stats().unwrap().nodes, 0)
+    }
+
+    #[test]
+    fn adding_object_rebuilds_bvh() {
+        let mut world = World::<f64>::with_acceleration(Acceleration::Bvh);
+        world.add_object(Box::new(Sphere::new(1)));
+        assert_eq!(world.bvh_stats().unwrap().nodes, 1);
+
+        let mut sphere = Sphere::new(2);
+        sphere
+            .set_transformation(Matrix::translation(0.0, 0.0, 10.0))
+            .unwrap();
+        world.add_object(Box::new(sphere));
+        let ray = Ray::new(
+            Tuple::new_point(0.0, 0.0, 15.0),
+            Tuple::new_vector(0.0, 0.0, 1.0),
+        );
+
+        assert_eq!(world.intersect_world(ray).len(), 0);
+        assert!(world.is_shadowed(
+            &PointLight::new(Tuple::new_point(0.0, 0.0, 20.0), Color::new(1.0, 1.0, 1.0)),
+            Tuple::new_point(0.0, 0.0, 5.0)
+        ))
+    }
+
+    #[test]
+    fn color_with_bvh_matches_color_without() {
+        let mut world = World::<f64>::with_acceleration(Acceleration::Bvh);
+        let test_world = World::<f64>::test_world();
+        test_world
+            .objects
+            .into_iter()
+            .for_each(|object| world.add_object(object));
+        world.lights = test_world.lights;
+        let ray = Ray::new(
+            Tuple::new_point(0.0, 0.0, -5.0),
+            Tuple::new_vector(0.0, 0.0, 1.0),
+        );
+
+        assert_eq!(
+            world.color_at(ray),
+            Color::new(0.38066119, 0.47582649, 0.28549589)
+        );
+        assert!(world.is_shadowed(
+            world.lights[0].as_ref(),
+            Tuple::new_point(10.0, -10.0, 10.0)
+        ))
+    }
+
+    #[test]
+    fn intersect_world_with_ray() {
+        let world = World::<f64>::test_world();
+        let ray = Ray::new(
+            Tuple::new_point(0.0, 0.0, -5.0),
+            Tuple::new_vector(0.0, 0.0, 1.0),
+        );
+        let intersections = world.intersect_world(ray);
+
+        assert_eq!(intersections.len(), 4);
+        assert_eq!(intersections[0].value, 4.0);
+        assert_eq!(intersections[1].value, 4.5);
+        assert_eq!(intersections[2].value, 5.5);
+        assert_eq!(intersections[3].value, 6.0)
+    }
+
+    #[test]
+    fn color_when_ray_misses() {
+        let world = World::<f64>::test_world();
+        let ray = Ray::new(
+            Tuple::new_point(0.0, 0.0, -5.0),
+            Tuple::new_vector(0.0, 1.0, 0.0),
+        );
+
+        assert_eq!(world.color_at(ray), Color::new(0.0, 0.0, 0.0))
+    }
+
+    #[test]
+    fn color_when_ray_misses_uses_background() {
+        let mut world = World::<f64>::test_world();
+        world.background = Color::new(0.2, 0.3, 0.4);
+        let ray = Ray::new(
+            Tuple::new_point(0.0, 0.0, -5.0),
+            Tuple::new_vector(0.0, 1.0, 0.0),
+        );
+
+        assert_eq!(world.color_at(ray), Color::new(0.2, 0.3, 0.4))
+    }
+
+    #[test]
+    fn color_when_ray_hits() {
+        let world = World::<f64>::test_world();
+        let ray = Ray::new(
+            Tuple::new_point(0.0, 0.0, -5.0),
+            Tuple::new_vector(0.0, 0.0, 1.0),
+        );
+
+        assert_eq!(
+            world.color_at(ray),
+            Color::new(0.38066119, 0.47582649, 0.28549589)
+        )
+    }
+
+    #[test]
+    fn color_with_intersection_behind_ray() {
+        let mut world = World::<f64>::test_world();
+        for object in world.objects.iter_mut() {
+            object.set_material(Material {
+                ambient: 1.0,
+                ..object.material().clone()
+            });
+        }
+        let inner_color = world.objects[1].material().color.clone();
+        let ray = Ray::new(
+            Tuple::new_point(0.0, 0.0, 0.75),
+            Tuple::new_vector(0.0, 0.0, -1.0),
+        );
+
+        assert_eq!(world.color_at(ray), inner_color)
+    }
+
+    #[test]
+    fn color_adds_every_light() {
+        let mut world = World::<f64>::new();
+        world.add_object(Box::new(Sphere::new(1)));
+        for _ in 0..2 {
+            world.add_light(Box::new(PointLight::new(
+                Tuple::new_point(0.0, 0.0, -10.0),
+                Color::new(0.5, 0.5, 0.5),
+            )));
+        }
+        let ray = Ray::new(
+            Tuple::new_point(0.0, 0.0, -5.0),
+            Tuple::new_vector(0.0, 0.0, 1.0),
+        );
+
+        assert_eq!(world.color_at(ray), Color::new(1.9, 1.9, 1.9))
+    }
+
+    #[test]
+    fn shade_intersection() {
+        let world = World::<f64>::test_world();
+        let ray = Ray::new(
+            Tuple::new_point(0.0, 0.0, -5.0),
+            Tuple::new_vector(0.0, 0.0, 1.0),
+        );
+        let hit = Intersection::new(4.0, world.objects[0].as_ref());
+        let comps = prepare_computations(&hit, ray, &Intersections::new(vec![hit]));
+
+        assert_eq!(
+            world.shade_hit(&comps, world.max_depth),
+            Color::new(0.38066119, 0.47582649, 0.28549589)
+        )
+    }
+
+    #[test]
+    fn shade_intersection_from_inside() {
+        let mut world = World::<f64>::test_world();
+        world.lights = vec![Box::new(PointLight::new(
+            Tuple::new_point(0.0, 0.25, 0.0),
+            Color::new(1.0, 1.0, 1.0),
+        ))];
+        let ray = Ray::new(
+            Tuple::new_point(0.0, 0.0, 0.0),
+            Tuple::new_vector(0.0, 0.0, 1.0),
+        );
+        let hit = Intersection::new(0.5, world.objects[1].as_ref());
+        let comps = prepare_computations(&hit, ray, &Intersections::new(vec![hit]));
+
+        assert_eq!(
+            world.shade_hit(&comps, world.max_depth),
+            Color::new(0.90498447, 0.90498447, 0.90498447)
+        )
+    }
+
+    #[test]
+    fn no_shadow_when_nothing_collinear() {
+        let world = World::<f64>::test_world();
+        let point = Tuple::new_point(0.0, 10.0, 0.0);
+
+        assert!(!world.is_shadowed(world.lights[0].as_ref(), point))
+    }
+
+    #[test]
+    fn shadow_when_object_between_point_and_light() {
+        let world = World::<f64>::test_world();
+        let point = Tuple::new_point(10.0, -10.0, 10.0);
+
+        assert!(world.is_shadowed(world.lights[0].as_ref(), point))
+    }
+
+    #[test]
+    fn no_shadow_when_object_behind_light() {
+        let world = World::<f64>::test_world();
+        let point = Tuple::new_point(-20.0, 20.0, -20.0);
+
+        assert!(!world.is_shadowed(world.lights[0].as_ref(), point))
+    }
+
+    #[test]
+    fn no_shadow_when_object_behind_point() {
+        let world = World::<f64>::test_world();
+        let point = Tuple::new_point(-2.0, 2.0, -2.0);
+
+        assert!(!world.is_shadowed(world.lights[0].as_ref(), point))
+    }
+
+    #[test]
+    fn shade_intersection_in_shadow() {
+        let mut world = World::<f64>::new();
+        world.add_light(Box::new(PointLight::new(
+            Tuple::new_point(0.0, 0.0, -10.0),
+            Color::new(1.0, 1.0, 1.0),
+        )));
+        world.add_object(Box::new(Sphere::new(1)));
+        let mut sphere = Sphere::new(2);
+        sphere
+            .set_transformation(Matrix::translation(0.0, 0.0, 10.0))
+            .unwrap();
+        world.add_object(Box::new(sphere));
+        let ray = Ray::new(
+            Tuple::new_point(0.0, 0.0, 5.0),
+            Tuple::new_vector(0.0, 0.0, 1.0),
+        );
+        let hit = Intersection::new(4.0, world.objects[1].as_ref());
+        let comps = prepare_computations(&hit, ray, &Intersections::new(vec![hit]));
+
+        assert_eq!(
+            world.shade_hit(&comps, world.max_depth),
+            Color::new(0.1, 0.1, 0.1)
+        )
+    }
+
+    // add a reflective plane below the spheres of the test world
+    fn world_with_mirror() -> World<f64> {
+        let mut world = World::<f64>::test_world();
+        let mut plane = Plane::new(3);
+        plane.set_material(Material {
+            reflective: 0.5,
+            ..Material::default()
+        });
+        plane
+            .set_transformation(Matrix::translation(0.0, -1.0, 0.0))
+            .unwrap();
+        world.add_object(Box::new(plane));
+        world
+    }
+
+    fn ray_to_mirror() -> Ray<f64> {
+        let value = std::f64::consts::FRAC_1_SQRT_2;
+        Ray::new(
+            Tuple::new_point(0.0, 0.0, -3.0),
+            Tuple::new_vector(0.0, -value, value),
+        )
+    }
+
+    #[test]
+    fn reflected_color_of_nonreflective_material() {
+        let mut world = World::<f64>::test_world();
+        let mut inner = world.objects[1].clone();
+        inner.set_material(Material {
+            ambient: 1.0,
+            ..Material::default()
+        });
+        world.objects[1] = inner;
+        let ray = Ray::new(
+            Tuple::new_point(0.0, 0.0, 0.0),
+            Tuple::new_vector(0.0, 0.0, 1.0),
+        );
+        let hit = Intersection::new(1.0, world.objects[1].as_ref());
+        let comps = prepare_computations(&hit, ray, &Intersections::new(vec![hit]));
+
+        assert_eq!(
+            world.reflected_color(&comps, world.max_depth),
+            Color::new(0.0, 0.0, 0.0)
+        )
+    }
+
+    #[test]
+    fn reflected_color_of_reflective_material() {
+        let world = world_with_mirror();
+        let hit = Intersection::new(std::f64::consts::SQRT_2, world.objects[2].as_ref());
+        let comps = prepare_computations(&hit, ray_to_mirror(), &Intersections::new(vec![hit]));
+
+        assert_eq!(
+            world.reflected_color(&comps, world.max_depth),
+            Color::new(0.19033220, 0.23791525, 0.14274915)
+        )
+    }
+
+    #[test]
+    fn shade_hit_with_reflective_material() {
+        let world = world_with_mirror();
+        let hit = Intersection::new(std::f64::consts::SQRT_2, world.objects[2].as_ref());
+        let comps = prepare_computations(&hit, ray_to_mirror(), &Intersections::new(vec![hit]));
+
+        assert_eq!(
+            world.shade_hit(&comps, world.max_depth),
+            Color::new(0.87675728, 0.92434033, 0.82917423)
+        )
+    }
+
+    #[test]
+    fn reflected_color_without_remaining_bounces() {
+        let world = world_with_mirror();
+        let hit = Intersection::new(std::f64::consts::SQRT_2, world.objects[2].as_ref());
+        let comps = prepare_computations(&hit, ray_to_mirror(), &Intersections::new(vec![hit]));
+
+        assert_eq!(world.reflected_color(&comps, 0), Color::new(0.0, 0.0, 0.0))
+    }
+
+    #[test]
+    fn color_between_parallel_mirrors_terminates() {
+        let mut world = World::<f64>::new();
+        world.add_light(Box::new(PointLight::new(
+            Tuple::new_point(0.0, 0.0, 0.0),
+            Color::new(1.0, 1.0, 1.0),
+        )));
+        let mirror = Material {
+            reflective: 1.0,
+            ..Material::default()
+        };
+        for (id, y) in [(1, -1.0), (2, 1.0)] {
+            let mut plane = Plane::new(id);
+            plane.set_material(mirror.clone());
+            plane
+                .set_transformation(Matrix::translation(0.0, y, 0.0))
+                .unwrap();
+            world.add_object(Box::new(plane));
+        }
+        let ray = Ray::new(
+            Tuple::new_point(0.0, 0.0, 0.0),
+            Tuple::new_vector(0.0, 1.0, 0.0),
+        );
+
+        // every bounce adds the light of the surface that is hit
+        assert_eq!(world.color_at(ray), Color::new(11.4, 11.4, 11.4))
+    }
+
+    #[test]
+    fn refracted_color_of_opaque_surface() {
+        let world = World::<f64>::test_world();
+        let ray = Ray::new(
+            Tuple::new_point(0.0, 0.0, -5.0),
+            Tuple::new_vector(0.0, 0.0, 1.0),
+        );
+        let object = world.objects[0].as_ref();
+        let intersections = Intersections::new(vec![
+            Intersection::new(4.0, object),
+            Intersection::new(6.0, object),
+        ]);
+        let comps = prepare_computations(&intersections[0], ray, &intersections);
+
+        assert_eq!(
+            world.refracted_color(&comps, world.max_depth),
+            Color::new(0.0, 0.0, 0.0)
+        )
+    }
+
+    #[test]
+    fn refracted_color_without_remaining_bounces() {
+        let mut world = World::<f64>::test_world();
+        let object = &mut world.objects[0];
+        object.set_material(Material {
+            transparency: 1.0,
+            refractive_index: 1.5,
+            ..object.material().clone()
+        });
+        let ray = Ray::new(
+            Tuple::new_point(0.0, 0.0, -5.0),
+            Tuple::new_vector(0.0, 0.0, 1.0),
+        );
+        let object = world.objects[0].as_ref();
+        let intersections = Intersections::new(vec![
+            Intersection::new(4.0, object),
+            Intersection::new(6.0, object),
+        ]);
+        let comps = prepare_computations(&intersections[0], ray, &intersections);
+
+        assert_eq!(world.refracted_color(&comps, 0), Color::new(0.0, 0.0, 0.0))
+    }
+
+    #[test]
+    fn refracted_color_under_total_internal_reflection() {
+        let value = std::f64::consts::FRAC_1_SQRT_2;
+        let mut world = World::<f64>::test_world();
+        let object = &mut world.objects[0];
+        object.set_material(Material {
+            transparency: 1.0,
+            refractive_index: 1.5,
+            ..object.material().clone()
+        });
+        let ray = Ray::new(
+            Tuple::new_point(0.0, 0.0, value),
+            Tuple::new_vector(0.0, 1.0, 0.0),
+        );
+        let object = world.objects[0].as_ref();
+        let intersections = Intersections::new(vec![
+            Intersection::new(-value, object),
+            Intersection::new(value, object),
+        ]);
+        // the ray starts inside of the sphere, so the second intersection is used
+        let comps = prepare_computations(&intersections[1], ray, &intersections);
+
+        assert_eq!(
+            world.refracted_color(&comps, world.max_depth),
+            Color::new(0.0, 0.0, 0.0)
+        )
+    }
+
+    #[test]
+    fn refracted_color_with_refracted_ray() {
+        let mut world = World::<f64>::test_world();
+        let object = &mut world.objects[0];
+        object.set_material(Material {
+            ambient: 1.0,
+            pattern: Some(Box::new(test_pattern())),
+            ..object.material().clone()
+        });
+        let object = &mut world.objects[1];
+        object.set_material(Material {
+            transparency: 1.0,
+            refractive_index: 1.5,
+            ..object.material().clone()
+        });
+        let ray = Ray::new(
+            Tuple::new_point(0.0, 0.0, 0.1),
+            Tuple::new_vector(0.0, 1.0, 0.0),
+        );
+        let (a, b) = (world.objects[0].as_ref(), world.objects[1].as_ref());
+        let intersections = Intersections::new(vec![
+            Intersection::new(-0.9899, a),
+            Intersection::new(-0.4899, b),
+            Intersection::new(0.4899, b),
+            Intersection::new(0.9899, a),
+        ]);
+        let comps = prepare_computations(&intersections[2], ray, &intersections);
+
+        assert_eq!(
+            world.refracted_color(&comps, world.max_depth),
+            Color::new(0.0, 0.99887455, 0.04721898)
+        )
+    }
+
+    // add a glass floor below the spheres of the test world
+    // and a red ball below the floor
+    fn world_with_glass_floor(reflective: f64) -> World<f64> {
+        let mut world = World::<f64>::test_world();
+        let mut floor = Plane::new(3);
+        floor
+            .set_transformation(Matrix::translation(0.0, -1.0, 0.0))
+            .unwrap();
+        floor.set_material(Material {
+            reflective,
+            transparency: 0.5,
+            refractive_index: 1.5,
+            ..Material::default()
+        });
+        let mut ball = Sphere::new(4);
+        ball.set_transformation(Matrix::translation(0.0, -3.5, -0.5))
+            .unwrap();
+        ball.set_material(Material {
+            color: Color::new(1.0, 0.0, 0.0),
+            ambient: 0.5,
+            ..Material::default()
+        });
+        world.add_object(Box::new(floor));
+        world.add_object(Box::new(ball));
+        world
+    }
+
+    #[test]
+    fn shade_hit_with_transparent_material() {
+        let world = world_with_glass_floor(0.0);
+        let hit = Intersection::new(std::f64::consts::SQRT_2, world.objects[2].as_ref());
+        let comps = prepare_computations(&hit, ray_to_mirror(), &Intersections::new(vec![hit]));
+
+        assert_eq!(
+            world.shade_hit(&comps, world.max_depth),
+            Color::new(0.93642508, 0.68642508, 0.68642508)
+        )
+    }
+
+    #[test]
+    fn shade_hit_with_reflective_transparent_material() {
+        let world = world_with_glass_floor(0.5);
+        let hit = Intersection::new(std::f64::consts::SQRT_2, world.objects[2].as_ref());
+        let comps = prepare_computations(&hit, ray_to_mirror(), &Intersections::new(vec![hit]));
+
+        assert_eq!(
+            world.shade_hit(&comps, world.max_depth),
+            Color::new(0.93391490, 0.69643400, 0.69243044)
+        )
+    }
+
+    #[test]
+    fn point_light_is_visible_or_hidden() {
+        let world = World::<f64>::test_world();
+        let light = world.lights[0].as_ref();
+        let parameters = [
+            ((-10.0, 10.0, -10.0), 1.0),
+            ((0.0, 1.0001, 0.0), 1.0),
+            ((-1.0001, 0.0, 0.0), 1.0),
+            ((0.0, 0.0, -1.0001), 1.0),
+            ((0.0, 0.0, 1.0001), 0.0),
+            ((1.0001, 0.0, 0.0), 0.0),
+            ((0.0, -1.0001, 0.0), 0.0),
+            ((0.0, 0.0, 0.0), 0.0),
+        ];
+
+        for ((x, y, z), visible) in parameters {
+            assert_eq!(
+                world.intensity_at(light, Tuple::new_point(x, y, z)),
+                visible
+            )
+        }
+    }
+
+    #[test]
+    fn area_light_casts_soft_shadows() {
+        let world = World::<f64>::test_world();
+        let light = AreaLight::new(
+            Tuple::new_point(-0.5, -0.5, -5.0),
+            Tuple::new_vector(1.0, 0.0, 0.0),
+            4,
+            Tuple::new_vector(0.0, 1.0, 0.0),
+            4,
+            Color::new(1.0, 1.0, 1.0),
+        );
+
+        // the point in the middle of the shadow sees nothing of the
+        // light, the point in front of the sphere sees all of it
+        assert_eq!(
+            world.intensity_at(&light, Tuple::new_point(0.0, 0.0, 2.0)),
+            0.0
+        );
+        assert_eq!(
+            world.intensity_at(&light, Tuple::new_point(0.0, 0.0, -2.0)),
+            1.0
+        );
+        // points at the edge of the shadow see a part of the light
+        for (x, y, z) in [(1.0, -1.0, 2.0), (1.5, 0.0, 2.0), (1.25, 1.25, 3.0)] {
+            let visible = world.intensity_at(&light, Tuple::new_point(x, y, z));
+            assert!(visible > 0.0 && visible < 1.0)
+        }
+        assert!(!world.is_shadowed(&light, Tuple::new_point(1.5, 0.0, 2.0)))
+    }
+
+    #[test]
+    fn spot_light_lights_only_inside_of_cone() {
+        let mut world = World::<f64>::test_world();
+        let ray = Ray::new(
+            Tuple::new_point(0.0, 0.0, -5.0),
+            Tuple::new_vector(0.0, 0.0, 1.0),
+        );
+        let point_color = world.color_at(ray);
+        let position = Tuple::new_point(-10.0, 10.0, -10.0);
+        world.lights = vec![Box::new(SpotLight::new(
+            position,
+            Tuple::new_point(0.0, 0.0, 0.0) - position,
+            0.2,
+            0.3,
+            Color::new(1.0, 1.0, 1.0),
+        ))];
+
+        // the hit is inside of the inner cone, so the spot light
+        // gives the same color as the point light in the same place
+        assert_eq!(world.color_at(ray), point_color);
+        assert!(world.is_shadowed(world.lights[0].as_ref(), Tuple::new_point(1.0001, 0.0, 0.0)));
+
+        world.lights = vec![Box::new(SpotLight::new(
+            position,
+            Tuple::new_vector(0.0, 0.0, 1.0),
+            0.2,
+            0.3,
+            Color::new(1.0, 1.0, 1.0),
+        ))];
+        // outside of the outer cone not even ambient light reaches the hit
+        assert_eq!(world.color_at(ray), Color::new(0.0, 0.0, 0.0))
+    }
+
+    #[test]
+    fn directional_light_shadows_reach_infinitely_far() {
+        for acceleration in [Acceleration::None, Acceleration::Bvh] {
+            let mut world = World::<f64>::with_acceleration(acceleration);
+            let mut sphere = Sphere::new(1);
+            sphere
+                .set_transformation(Matrix::translation(0.0, 1e7, 0.0))
+                .unwrap();
+            world.add_object(Box::new(sphere));
+            let light =
+                DirectionalLight::new(Tuple::new_vector(0.0, -1.0, 0.0), Color::new(1.0, 1.0, 1.0));
+
+            assert!(world.is_shadowed(&light, Tuple::new_point(0.0, 0.0, 0.0)));
+            assert!(!world.is_shadowed(&light, Tuple::new_point(2.0, 0.0, 0.0)));
+            // objects behind the point do not cast a shadow on it
+            assert!(!world.is_shadowed(&light, Tuple::new_point(0.0, 2e7, 0.0)))
+        }
+    }
+
+    #[test]
+    fn changing_objects_rebuilds_bvh() {
+        let mut world = World::<f64>::with_acceleration(Acceleration::Bvh);
+        for id in 0..4 {
+            let mut sphere = Sphere::new(id);
+            sphere
+                .set_transformation(Matrix::translation(id as f64 * 3.0, 0.0, 0.0))
+                .unwrap();
+            world.add_object(Box::new(sphere));
+        }
+        let ray = Ray::new(
+            Tuple::new_point(9.0, 0.0, -5.0),
+            Tuple::new_vector(0.0, 0.0, 1.0),
+        );
+        assert_eq!(world.intersect_world(ray).len(), 2);
+
+        world.objects_mut().truncate(2);
+
+        assert_eq!(world.objects().len(), 2);
+        assert_eq!(world.bvh_stats().unwrap().nodes, 1);
+        assert!(world.intersect_world(ray).is_empty())
+    }
+
+    #[test]
+    fn nearly_parallel_ray_hits_with_bvh() {
+        let mut world = World::<f64>::with_acceleration(Acceleration::Bvh);
+        world.add_object(Box::new(Sphere::new(1)));
+        let ray = Ray::new(
+            Tuple::new_point(1.00001, 0.0, -5.0),
+            Tuple::new_vector(-5e-6, 0.0, 1.0),
+        );
+
+        assert_eq!(world.intersect_world(ray).len(), 2);
+        assert!(world.any_hit(ray, 10.0))
+    }
+}